@@ -0,0 +1,355 @@
+use serde::{Deserialize, Serialize};
+use crate::layout::Rect;
+
+/// Smallest share of a split either child can be resized to
+pub const MIN_SPLIT_RATIO: f32 = 0.1;
+/// Largest share of a split either child can be resized to
+pub const MAX_SPLIT_RATIO: f32 = 0.9;
+
+/// How a split node divides its area between its two children.
+/// `Horizontal` places the children side by side, `Vertical` stacks them.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SplitDirection {
+    Horizontal,
+    Vertical,
+}
+
+impl SplitDirection {
+    fn flipped(self) -> Self {
+        match self {
+            SplitDirection::Horizontal => SplitDirection::Vertical,
+            SplitDirection::Vertical => SplitDirection::Horizontal,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum BspNode {
+    Leaf {
+        window_id: String,
+    },
+    Split {
+        direction: SplitDirection,
+        ratio: f32,
+        first: Box<BspNode>,
+        second: Box<BspNode>,
+    },
+}
+
+impl BspNode {
+    pub fn leaf(window_id: String) -> Self {
+        BspNode::Leaf { window_id }
+    }
+
+    pub fn contains(&self, window_id: &str) -> bool {
+        match self {
+            BspNode::Leaf { window_id: id } => id == window_id,
+            BspNode::Split { first, second, .. } => first.contains(window_id) || second.contains(window_id),
+        }
+    }
+
+    pub fn window_ids(&self) -> Vec<String> {
+        let mut ids = Vec::new();
+        self.collect_window_ids(&mut ids);
+        ids
+    }
+
+    fn collect_window_ids(&self, ids: &mut Vec<String>) {
+        match self {
+            BspNode::Leaf { window_id } => ids.push(window_id.clone()),
+            BspNode::Split { first, second, .. } => {
+                first.collect_window_ids(ids);
+                second.collect_window_ids(ids);
+            }
+        }
+    }
+
+    /// Splits the leaf holding `target` in two, keeping the existing window
+    /// first. Split directions alternate with depth, giving a dwindle spiral.
+    /// Returns false if `target` is not in the tree.
+    pub fn split_leaf(&mut self, target: &str, window_id: String) -> bool {
+        self.split_leaf_in(target, window_id, None)
+    }
+
+    /// Like `split_leaf`, but splits in `direction` when one is given
+    pub fn split_leaf_in(&mut self, target: &str, window_id: String, direction: Option<SplitDirection>) -> bool {
+        self.split_leaf_at(target, window_id, SplitDirection::Horizontal, direction)
+    }
+
+    fn split_leaf_at(
+        &mut self,
+        target: &str,
+        window_id: String,
+        direction: SplitDirection,
+        preselected: Option<SplitDirection>,
+    ) -> bool {
+        match self {
+            BspNode::Leaf { window_id: id } if id == target => {
+                let existing = std::mem::replace(self, BspNode::leaf(String::new()));
+                *self = BspNode::Split {
+                    direction: preselected.unwrap_or(direction),
+                    ratio: 0.5,
+                    first: Box::new(existing),
+                    second: Box::new(BspNode::leaf(window_id)),
+                };
+                true
+            }
+            BspNode::Leaf { .. } => false,
+            BspNode::Split { direction: split, first, second, .. } => {
+                let child_direction = split.flipped();
+                if first.contains(target) {
+                    first.split_leaf_at(target, window_id, child_direction, preselected)
+                } else if second.contains(target) {
+                    second.split_leaf_at(target, window_id, child_direction, preselected)
+                } else {
+                    false
+                }
+            }
+        }
+    }
+
+    /// Grows the leaf holding `window_id` by `delta` of the split it was made
+    /// by, shrinking its sibling. Returns false if the window is not in the
+    /// tree or fills it alone.
+    pub fn resize_split(&mut self, window_id: &str, delta: f32) -> bool {
+        match self.parent_split(window_id) {
+            Some((BspNode::Split { ratio, .. }, in_first)) => {
+                let delta = if in_first { delta } else { -delta };
+                *ratio = (*ratio + delta).clamp(MIN_SPLIT_RATIO, MAX_SPLIT_RATIO);
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Turns the split the leaf holding `window_id` was made by, so side by
+    /// side children are stacked and the other way round. Returns false if
+    /// the window is not in the tree or fills it alone.
+    pub fn rotate_split(&mut self, window_id: &str) -> bool {
+        match self.parent_split(window_id) {
+            Some((BspNode::Split { direction, .. }, _)) => {
+                *direction = direction.flipped();
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// The split one of whose children is the leaf holding `window_id`, and
+    /// whether it is the first child
+    fn parent_split(&mut self, window_id: &str) -> Option<(&mut BspNode, bool)> {
+        let holds = |node: &BspNode| matches!(node, BspNode::Leaf { window_id: id } if id == window_id);
+        let in_first = match self {
+            BspNode::Leaf { .. } => return None,
+            BspNode::Split { first, second, .. } => {
+                if holds(first) {
+                    Some(true)
+                } else if holds(second) {
+                    Some(false)
+                } else {
+                    None
+                }
+            }
+        };
+        if let Some(in_first) = in_first {
+            return Some((self, in_first));
+        }
+
+        match self {
+            BspNode::Split { first, .. } if first.contains(window_id) => first.parent_split(window_id),
+            BspNode::Split { second, .. } => second.parent_split(window_id),
+            BspNode::Leaf { .. } => None,
+        }
+    }
+
+    /// Exchanges the windows held by two leaves, keeping the tree shape
+    pub fn swap_windows(&mut self, a: &str, b: &str) {
+        match self {
+            BspNode::Leaf { window_id } => {
+                if window_id == a {
+                    *window_id = b.to_string();
+                } else if window_id == b {
+                    *window_id = a.to_string();
+                }
+            }
+            BspNode::Split { first, second, .. } => {
+                first.swap_windows(a, b);
+                second.swap_windows(a, b);
+            }
+        }
+    }
+
+    /// Removes the leaf holding `window_id`, collapsing its parent split into
+    /// the remaining sibling. Returns `None` when the removed leaf was the root.
+    pub fn remove(self, window_id: &str) -> Option<BspNode> {
+        match self {
+            BspNode::Leaf { window_id: ref id } if id == window_id => None,
+            BspNode::Leaf { .. } => Some(self),
+            BspNode::Split { direction, ratio, first, second } => {
+                if first.contains(window_id) {
+                    match first.remove(window_id) {
+                        Some(first) => Some(BspNode::Split { direction, ratio, first: Box::new(first), second }),
+                        None => Some(*second),
+                    }
+                } else if second.contains(window_id) {
+                    match second.remove(window_id) {
+                        Some(second) => Some(BspNode::Split { direction, ratio, first, second: Box::new(second) }),
+                        None => Some(*first),
+                    }
+                } else {
+                    Some(BspNode::Split { direction, ratio, first, second })
+                }
+            }
+        }
+    }
+
+    /// Computes the rectangle of every leaf inside `area`, leaving `gap`
+    /// pixels between siblings.
+    pub fn layout(&self, area: Rect, gap: u32) -> Vec<(String, Rect)> {
+        let mut rects = Vec::new();
+        self.layout_into(area, gap, &mut rects);
+        rects
+    }
+
+    fn layout_into(&self, area: Rect, gap: u32, rects: &mut Vec<(String, Rect)>) {
+        match self {
+            BspNode::Leaf { window_id } => rects.push((window_id.clone(), area)),
+            BspNode::Split { direction, ratio, first, second } => {
+                let (first_area, second_area) = match direction {
+                    SplitDirection::Horizontal => {
+                        let usable = area.width.saturating_sub(gap);
+                        let first_width = (usable as f32 * ratio) as u32;
+                        (
+                            Rect { width: first_width, ..area },
+                            Rect {
+                                x: area.x + first_width.saturating_add(gap) as i32,
+                                width: usable.saturating_sub(first_width),
+                                ..area
+                            },
+                        )
+                    }
+                    SplitDirection::Vertical => {
+                        let usable = area.height.saturating_sub(gap);
+                        let first_height = (usable as f32 * ratio) as u32;
+                        (
+                            Rect { height: first_height, ..area },
+                            Rect {
+                                y: area.y + first_height.saturating_add(gap) as i32,
+                                height: usable.saturating_sub(first_height),
+                                ..area
+                            },
+                        )
+                    }
+                };
+                first.layout_into(first_area, gap, rects);
+                second.layout_into(second_area, gap, rects);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const AREA: Rect = Rect { x: 0, y: 0, width: 1000, height: 1000 };
+
+    /// a, b, c and d, each splitting the leaf of the one before
+    fn dwindle() -> BspNode {
+        let mut tree = BspNode::leaf("a".to_string());
+        for (target, window_id) in [("a", "b"), ("b", "c"), ("c", "d")] {
+            assert!(tree.split_leaf(target, window_id.to_string()));
+        }
+        tree
+    }
+
+    fn rect(x: i32, y: i32, width: u32, height: u32) -> Rect {
+        Rect { x, y, width, height }
+    }
+
+    #[test]
+    fn splits_alternate_direction_with_depth() {
+        let tree = dwindle();
+        assert_eq!(tree.window_ids(), ["a", "b", "c", "d"]);
+        assert_eq!(
+            tree.layout(AREA, 0),
+            [
+                ("a".to_string(), rect(0, 0, 500, 1000)),
+                ("b".to_string(), rect(500, 0, 500, 500)),
+                ("c".to_string(), rect(500, 500, 250, 500)),
+                ("d".to_string(), rect(750, 500, 250, 500)),
+            ]
+        );
+    }
+
+    #[test]
+    fn splitting_a_missing_leaf_changes_nothing() {
+        let mut tree = dwindle();
+        assert!(!tree.split_leaf("missing", "e".to_string()));
+        assert_eq!(tree.window_ids(), ["a", "b", "c", "d"]);
+    }
+
+    #[test]
+    fn removing_a_leaf_collapses_its_parent() {
+        // b's sibling, the split holding c and d, takes the place of their parent
+        let tree = dwindle().remove("b").unwrap();
+        assert_eq!(
+            tree.layout(AREA, 0),
+            [
+                ("a".to_string(), rect(0, 0, 500, 1000)),
+                ("c".to_string(), rect(500, 0, 250, 1000)),
+                ("d".to_string(), rect(750, 0, 250, 1000)),
+            ]
+        );
+
+        let tree = tree.remove("missing").unwrap();
+        assert_eq!(tree.window_ids(), ["a", "c", "d"]);
+        assert!(BspNode::leaf("a".to_string()).remove("a").is_none());
+    }
+
+    #[test]
+    fn splits_can_be_resized_and_rotated() {
+        let mut tree = dwindle();
+        // d is the second child of the split it shares with c
+        assert!(tree.resize_split("d", 0.25));
+        assert!(tree.rotate_split("a"));
+        assert_eq!(
+            tree.layout(AREA, 0),
+            [
+                ("a".to_string(), rect(0, 0, 1000, 500)),
+                ("b".to_string(), rect(0, 500, 1000, 250)),
+                ("c".to_string(), rect(0, 750, 250, 250)),
+                ("d".to_string(), rect(250, 750, 750, 250)),
+            ]
+        );
+
+        // Ratios stop short of squeezing a child out
+        assert!(tree.resize_split("a", -5.0));
+        assert_eq!(tree.layout(AREA, 0)[0].1, rect(0, 0, 1000, 100));
+
+        assert!(!tree.resize_split("missing", 0.1));
+        assert!(!BspNode::leaf("a".to_string()).rotate_split("a"));
+    }
+
+    #[test]
+    fn preselected_direction_overrides_the_spiral() {
+        let mut tree = BspNode::leaf("a".to_string());
+        assert!(tree.split_leaf_in("a", "b".to_string(), Some(SplitDirection::Vertical)));
+        assert_eq!(
+            tree.layout(AREA, 0),
+            [("a".to_string(), rect(0, 0, 1000, 500)), ("b".to_string(), rect(0, 500, 1000, 500))]
+        );
+    }
+
+    #[test]
+    fn swapping_keeps_the_shape() {
+        let mut tree = dwindle();
+        let before = tree.layout(AREA, 10);
+        tree.swap_windows("a", "d");
+
+        let after = tree.layout(AREA, 10);
+        assert_eq!(tree.window_ids(), ["d", "b", "c", "a"]);
+        let rects = |layout: &[(String, Rect)]| layout.iter().map(|(_, rect)| *rect).collect::<Vec<_>>();
+        assert_eq!(rects(&before), rects(&after));
+    }
+}
@@ -1,9 +1,12 @@
 use tauri::State;
 use crate::window_manager::{WindowManager, ManagedWindow};
-use crate::workspace::Workspace;
-use crate::layout::LayoutType;
+use crate::workspace::{OrphanPolicy, Workspace};
+use crate::bsp::SplitDirection;
+use crate::layout::{Direction, LayoutType, TabInfo};
 use crate::config::Config;
-use crate::system_window::SystemWindow;
+use crate::system_window::{SystemWindow, WindowStateFlag};
+use crate::scratchpad::Scratchpad;
+use crate::monitor::{Monitor, Strut};
 
 #[tauri::command]
 pub fn get_windows(wm: State<WindowManager>) -> Vec<ManagedWindow> {
@@ -24,16 +27,88 @@ pub fn remove_window_from_manager(wm: State<WindowManager>, window_id: String) -
     wm.remove_window(&window_id)
 }
 
+#[tauri::command]
+pub fn move_window_to_workspace(
+    wm: State<WindowManager>,
+    window_id: String,
+    workspace_id: String,
+    follow: bool
+) -> Result<(), String> {
+    wm.move_window_to_workspace(&window_id, &workspace_id, follow)
+}
+
+#[tauri::command]
+pub fn toggle_sticky(wm: State<WindowManager>, window_id: String) -> Result<bool, String> {
+    wm.toggle_sticky(&window_id)
+}
+
+#[tauri::command]
+pub fn manage_system_window(wm: State<WindowManager>, handle: u64) -> Result<String, String> {
+    wm.manage_system_window(handle)
+}
+
+#[tauri::command]
+pub fn toggle_floating(wm: State<WindowManager>, window_id: String) -> Result<bool, String> {
+    wm.toggle_floating(&window_id)
+}
+
+#[tauri::command]
+pub fn center_floating_window(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
+    wm.center_floating_window(&window_id)
+}
+
+#[tauri::command]
+pub fn send_to_scratchpad(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
+    wm.send_to_scratchpad(&window_id)
+}
+
+#[tauri::command]
+pub fn send_system_window_to_scratchpad(wm: State<WindowManager>, handle: u64) -> Result<(), String> {
+    wm.send_system_window_to_scratchpad(handle)
+}
+
+#[tauri::command]
+pub fn remove_system_window_from_scratchpad(wm: State<WindowManager>, handle: u64) -> Result<(), String> {
+    wm.remove_system_window_from_scratchpad(handle)
+}
+
+#[tauri::command]
+pub fn toggle_scratchpad(wm: State<WindowManager>) -> Result<bool, String> {
+    wm.toggle_scratchpad()
+}
+
+#[tauri::command]
+pub fn get_scratchpad(wm: State<WindowManager>) -> Scratchpad {
+    wm.get_scratchpad()
+}
+
 #[tauri::command]
 pub fn create_workspace(wm: State<WindowManager>, name: String, layout: String) -> Result<String, String> {
-    let layout_type = match layout.as_str() {
-        "tiling" => LayoutType::Tiling,
-        "floating" => LayoutType::Floating,
-        "monocle" => LayoutType::Monocle,
-        _ => return Err("Invalid layout type".to_string()),
-    };
+    if !wm.has_layout(&layout) {
+        return Err("Invalid layout type".to_string());
+    }
     
-    Ok(wm.create_workspace(name, layout_type))
+    Ok(wm.create_workspace(name, LayoutType::from_name(&layout)))
+}
+
+#[tauri::command]
+pub fn get_layouts(wm: State<WindowManager>) -> Vec<String> {
+    wm.get_layout_names()
+}
+
+#[tauri::command]
+pub fn get_tabs(wm: State<WindowManager>, workspace_id: String) -> Result<Vec<TabInfo>, String> {
+    wm.get_tabs(&workspace_id)
+}
+
+#[tauri::command]
+pub fn set_layout_option(
+    wm: State<WindowManager>,
+    workspace_id: String,
+    key: String,
+    value: serde_json::Value
+) -> Result<(), String> {
+    wm.set_layout_option(&workspace_id, key, value)
 }
 
 #[tauri::command]
@@ -41,16 +116,80 @@ pub fn switch_workspace(wm: State<WindowManager>, workspace_id: String) -> Resul
     wm.switch_workspace(&workspace_id)
 }
 
+#[tauri::command]
+pub fn switch_workspace_by_number(wm: State<WindowManager>, number: usize) -> Result<String, String> {
+    wm.switch_workspace_by_number(number)
+}
+
+#[tauri::command]
+pub fn rename_workspace(wm: State<WindowManager>, workspace_id: String, name: String) -> Result<(), String> {
+    wm.rename_workspace(&workspace_id, name)
+}
+
+#[tauri::command]
+pub fn move_workspace(wm: State<WindowManager>, workspace_id: String, index: usize) -> Result<(), String> {
+    wm.move_workspace(&workspace_id, index)
+}
+
+#[tauri::command]
+pub fn delete_workspace(wm: State<WindowManager>, workspace_id: String, policy: OrphanPolicy) -> Result<(), String> {
+    wm.delete_workspace(&workspace_id, policy)
+}
+
 #[tauri::command]
 pub fn get_workspaces(wm: State<WindowManager>) -> Vec<Workspace> {
     wm.get_workspaces()
 }
 
+#[tauri::command]
+pub fn get_workspace_by_name(wm: State<WindowManager>, name: String) -> Option<Workspace> {
+    wm.get_workspace_by_name(&name)
+}
+
 #[tauri::command]
 pub fn arrange_windows(wm: State<WindowManager>, workspace_id: String) -> Result<(), String> {
     wm.arrange_workspace(&workspace_id)
 }
 
+#[tauri::command]
+pub fn adjust_master_ratio(wm: State<WindowManager>, workspace_id: String, delta: f32) -> Result<(), String> {
+    wm.adjust_master_ratio(&workspace_id, delta)
+}
+
+#[tauri::command]
+pub fn adjust_master_count(wm: State<WindowManager>, workspace_id: String, delta: i32) -> Result<(), String> {
+    wm.adjust_master_count(&workspace_id, delta)
+}
+
+#[tauri::command]
+pub fn resize_bsp_split(wm: State<WindowManager>, window_id: String, delta: f32) -> Result<(), String> {
+    wm.resize_bsp_split(&window_id, delta)
+}
+
+#[tauri::command]
+pub fn rotate_bsp_split(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
+    wm.rotate_bsp_split(&window_id)
+}
+
+#[tauri::command]
+pub fn preselect_bsp_split(
+    wm: State<WindowManager>,
+    workspace_id: String,
+    direction: Option<SplitDirection>,
+) -> Result<(), String> {
+    wm.preselect_bsp_split(&workspace_id, direction)
+}
+
+#[tauri::command]
+pub fn resize_tile(wm: State<WindowManager>, window_id: String, direction: Direction, delta: i32) -> Result<(), String> {
+    wm.resize_tile(&window_id, direction, delta)
+}
+
+#[tauri::command]
+pub fn reset_tile_weights(wm: State<WindowManager>, workspace_id: String) -> Result<(), String> {
+    wm.reset_tile_weights(&workspace_id)
+}
+
 #[tauri::command]
 pub fn close_window(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
     wm.remove_window(&window_id)
@@ -58,24 +197,12 @@ pub fn close_window(wm: State<WindowManager>, window_id: String) -> Result<(), S
 
 #[tauri::command]
 pub fn minimize_window(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
-    // In a real implementation, this would minimize the actual window
-    // For now, we'll just update the state
-    if let Some(window) = wm.windows.lock().unwrap().get_mut(&window_id) {
-        window.state = crate::window_manager::WindowState::Minimized;
-        Ok(())
-    } else {
-        Err("Window not found".to_string())
-    }
+    wm.minimize_window(&window_id)
 }
 
 #[tauri::command]
 pub fn maximize_window(wm: State<WindowManager>, window_id: String) -> Result<(), String> {
-    if let Some(window) = wm.windows.lock().unwrap().get_mut(&window_id) {
-        window.state = crate::window_manager::WindowState::Maximized;
-        Ok(())
-    } else {
-        Err("Window not found".to_string())
-    }
+    wm.maximize_window(&window_id)
 }
 
 #[tauri::command]
@@ -83,6 +210,49 @@ pub fn focus_window(wm: State<WindowManager>, window_id: String) -> Result<(), S
     wm.focus_window(&window_id)
 }
 
+#[tauri::command]
+pub fn focus_next(wm: State<WindowManager>) -> Result<String, String> {
+    wm.focus_next()
+}
+
+#[tauri::command]
+pub fn focus_prev(wm: State<WindowManager>) -> Result<String, String> {
+    wm.focus_prev()
+}
+
+#[tauri::command]
+pub fn focus_direction(wm: State<WindowManager>, direction: Direction) -> Result<String, String> {
+    wm.focus_direction(direction)
+}
+
+#[tauri::command]
+pub fn swap_direction(wm: State<WindowManager>, direction: Direction) -> Result<(), String> {
+    wm.swap_direction(direction)
+}
+
+#[tauri::command]
+pub fn get_monitors(wm: State<WindowManager>) -> Result<Vec<Monitor>, String> {
+    wm.get_monitors()
+}
+
+#[tauri::command]
+pub fn set_workspace_monitor(
+    wm: State<WindowManager>,
+    workspace_id: String,
+    monitor_id: Option<String>
+) -> Result<(), String> {
+    wm.set_workspace_monitor(&workspace_id, monitor_id)
+}
+
+#[tauri::command]
+pub fn reserve_strut(
+    wm: State<WindowManager>,
+    monitor_id: Option<String>,
+    strut: Strut
+) -> Result<(), String> {
+    wm.reserve_strut(monitor_id, strut)
+}
+
 #[tauri::command]
 pub fn get_config(wm: State<WindowManager>) -> Config {
     wm.config.lock().unwrap().clone()
@@ -90,7 +260,7 @@ pub fn get_config(wm: State<WindowManager>) -> Config {
 
 #[tauri::command]
 pub fn update_config(wm: State<WindowManager>, config: Config) -> Result<(), String> {
-    *wm.config.lock().unwrap() = config;
+    wm.update_config(config);
     Ok(())
 }
 
@@ -130,6 +300,11 @@ pub fn restore_system_window(wm: State<WindowManager>, handle: u64) -> Result<()
     wm.restore_system_window(handle)
 }
 
+#[tauri::command]
+pub fn set_system_window_state(wm: State<WindowManager>, handle: u64, flag: WindowStateFlag, enabled: bool) -> Result<(), String> {
+    wm.set_system_window_state(handle, flag, enabled)
+}
+
 #[tauri::command]
 pub fn close_system_window(wm: State<WindowManager>, handle: u64) -> Result<(), String> {
     wm.close_system_window(handle)
@@ -153,4 +328,28 @@ pub fn show_system_window(wm: State<WindowManager>, handle: u64) -> Result<(), S
 #[tauri::command]
 pub fn arrange_system_windows(wm: State<WindowManager>, window_handles: Vec<u64>) -> Result<(), String> {
     wm.arrange_system_windows(&window_handles)
+}
+
+#[tauri::command]
+pub fn arrange_system_windows_with_layout(
+    wm: State<WindowManager>,
+    window_handles: Vec<u64>,
+    layout: String,
+    active_handle: Option<u64>
+) -> Result<(), String> {
+    if !wm.has_layout(&layout) {
+        return Err("Invalid layout type".to_string());
+    }
+
+    wm.arrange_system_windows_with_layout(&window_handles, &layout, active_handle)
+}
+
+#[tauri::command]
+pub fn get_system_window_tabs(
+    wm: State<WindowManager>,
+    window_handles: Vec<u64>,
+    layout: String,
+    active_handle: Option<u64>
+) -> Vec<TabInfo> {
+    wm.get_system_window_tabs(&window_handles, &layout, active_handle)
 }
\ No newline at end of file
@@ -1,4 +1,5 @@
 use serde::{Deserialize, Serialize};
+use crate::rules::WindowRule;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Config {
@@ -11,6 +12,9 @@ pub struct Config {
     pub border_color_active: String,
     pub border_color_inactive: String,
     pub keybindings: KeyBindings,
+    /// Evaluated in order whenever a window is added or discovered
+    #[serde(default)]
+    pub rules: Vec<WindowRule>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -37,6 +41,7 @@ impl Default for Config {
             border_color_active: "#0066cc".to_string(),
             border_color_inactive: "#666666".to_string(),
             keybindings: KeyBindings::default(),
+            rules: Vec::new(),
         }
     }
 }
@@ -0,0 +1,61 @@
+use serde::{Deserialize, Serialize};
+use crate::layout::Rect;
+
+// Tauri events emitted by `WindowManager` whenever its state changes. Payloads
+// are either the changed object itself or one of the structs below.
+
+/// Payload: `ManagedWindow`
+pub const WINDOW_ADDED: &str = "window-added";
+/// Payload: `WindowRemoved`
+pub const WINDOW_REMOVED: &str = "window-removed";
+/// Payload: `ManagedWindow`, e.g. after moving it or toggling sticky/floating
+pub const WINDOW_UPDATED: &str = "window-updated";
+/// Payload: `WindowFocused`
+pub const WINDOW_FOCUSED: &str = "window-focused";
+/// Payload: `WindowsArranged`
+pub const WINDOWS_ARRANGED: &str = "windows-arranged";
+/// Payload: `Workspace`
+pub const WORKSPACE_CREATED: &str = "workspace-created";
+/// Payload: `Workspace`, e.g. after renaming it
+pub const WORKSPACE_UPDATED: &str = "workspace-updated";
+/// Payload: `WorkspaceDeleted`
+pub const WORKSPACE_DELETED: &str = "workspace-deleted";
+/// Payload: `WorkspaceSwitched`
+pub const WORKSPACE_SWITCHED: &str = "workspace-switched";
+/// Payload: workspace ids in their new order
+pub const WORKSPACES_REORDERED: &str = "workspaces-reordered";
+/// Payload: `Workspace` whose layout parameters changed
+pub const LAYOUT_CHANGED: &str = "layout-changed";
+/// Payload: `Config`
+pub const CONFIG_UPDATED: &str = "config-updated";
+/// Payload: `Scratchpad`
+pub const SCRATCHPAD_CHANGED: &str = "scratchpad-changed";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowRemoved {
+    pub window_id: String,
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowFocused {
+    pub window_id: String,
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WindowsArranged {
+    pub workspace_id: String,
+    pub positions: Vec<(String, Rect)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceDeleted {
+    pub workspace_id: String,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WorkspaceSwitched {
+    pub workspace_id: String,
+    pub previous: String,
+}
@@ -0,0 +1,120 @@
+use std::sync::Arc;
+use crate::events::{WindowFocused, WindowRemoved, WindowsArranged, WorkspaceSwitched};
+use crate::window_manager::ManagedWindow;
+use crate::workspace::Workspace;
+
+/// What a "before" hook decides about the operation it was called for
+#[derive(Debug, Clone, PartialEq)]
+pub enum HookResult {
+    Continue,
+    /// Cancels the operation, which then fails with this message. A vetoed
+    /// arrangement is skipped instead.
+    Veto(String),
+}
+
+/// Runs before an operation and may change its input or veto it
+pub(crate) type BeforeHook<T> = Arc<dyn Fn(&mut T) -> HookResult + Send + Sync>;
+/// Runs after an operation succeeded
+pub(crate) type AfterHook<T> = Arc<dyn Fn(&T) + Send + Sync>;
+
+/// A window about to be added. Hooks may rename it or redirect it to another
+/// workspace (id or name); window rules have already been applied.
+#[derive(Debug, Clone)]
+pub struct WindowAdd {
+    pub title: String,
+    pub app_name: String,
+    pub workspace_id: String,
+}
+
+/// Hooks called by `WindowManager` at its lifecycle points. "Before" hooks run
+/// in registration order, each seeing the changes of the previous ones, and
+/// the first veto wins. Hooks run without any `WindowManager` lock held, so
+/// they may call back into it. Register them with the `on_*` methods of
+/// `WindowManager`.
+#[derive(Default, Clone)]
+pub struct Hooks {
+    pub(crate) before_window_add: Vec<BeforeHook<WindowAdd>>,
+    pub(crate) after_window_add: Vec<AfterHook<ManagedWindow>>,
+    /// Receives the id of the window to remove
+    pub(crate) before_window_remove: Vec<BeforeHook<String>>,
+    pub(crate) after_window_remove: Vec<AfterHook<WindowRemoved>>,
+    /// Receives the id of the window to focus; changing it focuses another window
+    pub(crate) before_focus: Vec<BeforeHook<String>>,
+    pub(crate) after_focus: Vec<AfterHook<WindowFocused>>,
+    /// Receives the id of the workspace to switch to
+    pub(crate) before_workspace_switch: Vec<BeforeHook<String>>,
+    pub(crate) after_workspace_switch: Vec<AfterHook<WorkspaceSwitched>>,
+    /// Receives the snapshot the layout will be computed from; changes only
+    /// affect this arrangement, and a veto skips it without failing the
+    /// operation that asked for it
+    pub(crate) before_arrange: Vec<BeforeHook<Workspace>>,
+    pub(crate) after_arrange: Vec<AfterHook<WindowsArranged>>,
+}
+
+impl Hooks {
+    /// Runs before a window is added; may rename or redirect it
+    pub fn on_before_window_add(&mut self, hook: impl Fn(&mut WindowAdd) -> HookResult + Send + Sync + 'static) {
+        self.before_window_add.push(Arc::new(hook));
+    }
+
+    /// Runs after a window was added
+    pub fn on_after_window_add(&mut self, hook: impl Fn(&ManagedWindow) + Send + Sync + 'static) {
+        self.after_window_add.push(Arc::new(hook));
+    }
+
+    /// Runs before a window is removed, with its id
+    pub fn on_before_window_remove(&mut self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.before_window_remove.push(Arc::new(hook));
+    }
+
+    /// Runs after a window was removed
+    pub fn on_after_window_remove(&mut self, hook: impl Fn(&WindowRemoved) + Send + Sync + 'static) {
+        self.after_window_remove.push(Arc::new(hook));
+    }
+
+    /// Runs before a window is focused; changing the id focuses another window
+    pub fn on_before_focus(&mut self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.before_focus.push(Arc::new(hook));
+    }
+
+    /// Runs after a window was focused
+    pub fn on_after_focus(&mut self, hook: impl Fn(&WindowFocused) + Send + Sync + 'static) {
+        self.after_focus.push(Arc::new(hook));
+    }
+
+    /// Runs before switching to the workspace with the given id
+    pub fn on_before_workspace_switch(&mut self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.before_workspace_switch.push(Arc::new(hook));
+    }
+
+    /// Runs after the active workspace changed
+    pub fn on_after_workspace_switch(&mut self, hook: impl Fn(&WorkspaceSwitched) + Send + Sync + 'static) {
+        self.after_workspace_switch.push(Arc::new(hook));
+    }
+
+    /// Runs on the snapshot a workspace is about to be arranged from; a veto
+    /// leaves the windows where they are
+    pub fn on_before_arrange(&mut self, hook: impl Fn(&mut Workspace) -> HookResult + Send + Sync + 'static) {
+        self.before_arrange.push(Arc::new(hook));
+    }
+
+    /// Runs after a workspace was arranged
+    pub fn on_after_arrange(&mut self, hook: impl Fn(&WindowsArranged) + Send + Sync + 'static) {
+        self.after_arrange.push(Arc::new(hook));
+    }
+
+    pub(crate) fn run_before<T>(hooks: &[BeforeHook<T>], value: &mut T) -> Result<(), String> {
+        for hook in hooks {
+            if let HookResult::Veto(reason) = hook(value) {
+                return Err(reason);
+            }
+        }
+        Ok(())
+    }
+
+    pub(crate) fn run_after<T>(hooks: &[AfterHook<T>], value: &T) {
+        for hook in hooks {
+            hook(value);
+        }
+    }
+}
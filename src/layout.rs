@@ -1,10 +1,142 @@
 use serde::{Deserialize, Serialize};
+use crate::workspace::Workspace;
+
+/// Height of the tab strip reserved by the tabbed and stacked layouts when the
+/// workspace does not set a `header_height` layout option
+pub const DEFAULT_TAB_HEADER_HEIGHT: u32 = 24;
+
+/// Smallest width or height `resize_tile` will shrink a tile to
+pub const MIN_TILE_SIZE: u32 = 50;
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Direction {
+    Left,
+    Right,
+    Up,
+    Down,
+}
+
+/// Relative share of its row (`width`) and of the screen height (`height`)
+/// a window gets in the tiling layout
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct TileWeight {
+    pub width: f32,
+    pub height: f32,
+}
+
+impl Default for TileWeight {
+    fn default() -> Self {
+        Self {
+            width: 1.0,
+            height: 1.0,
+        }
+    }
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub enum LayoutType {
     Tiling,
     Floating,
     Monocle,
+    MasterStack,
+    Bsp,
+    CenteredMaster,
+    ThreeColumn,
+    Tabbed,
+    Stacked,
+    /// A layout registered at runtime through `WindowManager::register_layout`
+    Custom(String),
+}
+
+impl LayoutType {
+    /// Name the layout is registered under in the `WindowManager` layout registry
+    pub fn name(&self) -> &str {
+        match self {
+            LayoutType::Tiling => "tiling",
+            LayoutType::Floating => "floating",
+            LayoutType::Monocle => "monocle",
+            LayoutType::MasterStack => "master_stack",
+            LayoutType::Bsp => "bsp",
+            LayoutType::CenteredMaster => "centered_master",
+            LayoutType::ThreeColumn => "three_column",
+            LayoutType::Tabbed => "tabbed",
+            LayoutType::Stacked => "stacked",
+            LayoutType::Custom(name) => name,
+        }
+    }
+
+    pub fn from_name(name: &str) -> Self {
+        match name {
+            "tiling" => LayoutType::Tiling,
+            "floating" => LayoutType::Floating,
+            "monocle" => LayoutType::Monocle,
+            "master_stack" => LayoutType::MasterStack,
+            "bsp" => LayoutType::Bsp,
+            "centered_master" => LayoutType::CenteredMaster,
+            "three_column" => LayoutType::ThreeColumn,
+            "tabbed" => LayoutType::Tabbed,
+            "stacked" => LayoutType::Stacked,
+            _ => LayoutType::Custom(name.to_string()),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct Rect {
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
+}
+
+impl Rect {
+    fn center(&self) -> (i64, i64) {
+        (
+            self.x as i64 + self.width as i64 / 2,
+            self.y as i64 + self.height as i64 / 2,
+        )
+    }
+}
+
+/// Picks the candidate nearest to `from` in `direction`, comparing window
+/// centers. Candidates overlapping `from` on the perpendicular axis win over
+/// ones that are only diagonally in that direction.
+pub fn neighbor_in_direction(from: Rect, candidates: &[(String, Rect)], direction: Direction) -> Option<String> {
+    let (fx, fy) = from.center();
+
+    candidates
+        .iter()
+        .filter_map(|(window_id, rect)| {
+            let (cx, cy) = rect.center();
+            let (primary, secondary, overlaps) = match direction {
+                Direction::Left => (fx - cx, (cy - fy).abs(), spans_overlap(from.y, from.height, rect.y, rect.height)),
+                Direction::Right => (cx - fx, (cy - fy).abs(), spans_overlap(from.y, from.height, rect.y, rect.height)),
+                Direction::Up => (fy - cy, (cx - fx).abs(), spans_overlap(from.x, from.width, rect.x, rect.width)),
+                Direction::Down => (cy - fy, (cx - fx).abs(), spans_overlap(from.x, from.width, rect.x, rect.width)),
+            };
+
+            if primary <= 0 {
+                return None;
+            }
+
+            Some((!overlaps, primary * primary + secondary * secondary, window_id))
+        })
+        .min_by_key(|(not_overlapping, distance, _)| (*not_overlapping, *distance))
+        .map(|(_, _, window_id)| window_id.clone())
+}
+
+fn spans_overlap(a_start: i32, a_len: u32, b_start: i32, b_len: u32) -> bool {
+    a_start < b_start + b_len as i32 && b_start < a_start + a_len as i32
+}
+
+/// Tab bar entry for a window arranged by the tabbed or stacked layout
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TabInfo {
+    pub window_id: String,
+    pub index: usize,
+    pub title: String,
+    pub active: bool,
+    pub header: Rect,
 }
 
 impl Default for LayoutType {
@@ -19,6 +151,564 @@ impl std::fmt::Display for LayoutType {
             LayoutType::Tiling => write!(f, "Tiling"),
             LayoutType::Floating => write!(f, "Floating"),
             LayoutType::Monocle => write!(f, "Monocle"),
+            LayoutType::MasterStack => write!(f, "MasterStack"),
+            LayoutType::Bsp => write!(f, "Bsp"),
+            LayoutType::CenteredMaster => write!(f, "CenteredMaster"),
+            LayoutType::ThreeColumn => write!(f, "ThreeColumn"),
+            LayoutType::Tabbed => write!(f, "Tabbed"),
+            LayoutType::Stacked => write!(f, "Stacked"),
+            LayoutType::Custom(name) => write!(f, "{}", name),
+        }
+    }
+}
+
+/// A layout algorithm that turns an ordered list of windows into rectangles.
+///
+/// `area` is the screen area available to the workspace and `workspace` carries
+/// the per-workspace layout state (master ratio, BSP tree, `layout_options`).
+/// Windows left out of the result keep their current position and size.
+pub trait Layout: Send + Sync {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)>;
+
+    /// Whether the arrangement depends on the focused window, so that
+    /// changing focus re-arranges the workspace
+    fn depends_on_focus(&self) -> bool {
+        false
+    }
+}
+
+pub struct TilingLayout;
+
+impl Layout for TilingLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let count = window_ids.len();
+
+        if count == 0 {
+            return Vec::new();
+        }
+
+        if count == 1 {
+            return vec![(
+                window_ids[0].clone(),
+                Rect {
+                    x: area.x + gap as i32,
+                    y: area.y + gap as i32,
+                    width: area.width.saturating_sub(gap.saturating_mul(2)),
+                    height: area.height.saturating_sub(gap.saturating_mul(2)),
+                },
+            )];
+        }
+
+        let (cols, rows) = tiling_grid(count);
+
+        // Gaps can take up the whole area when it is tiny or very crowded
+        let usable_width = area.width.saturating_sub(gap.saturating_mul(cols as u32 + 1));
+        let usable_height = area.height.saturating_sub(gap.saturating_mul(rows as u32 + 1));
+
+        // A row is as tall as the average height weight of its windows
+        let row_weights: Vec<f32> = window_ids
+            .chunks(cols)
+            .map(|row| row.iter().map(|id| workspace.tile_weight(id).height).sum::<f32>() / row.len() as f32)
+            .collect();
+        let row_heights = distribute(usable_height, &row_weights);
+
+        let mut rects = Vec::new();
+        let mut y = area.y + gap as i32;
+
+        for (row_ids, height) in window_ids.chunks(cols).zip(row_heights) {
+            // Empty cells in a short last row keep their share of the width
+            let mut weights: Vec<f32> = row_ids.iter().map(|id| workspace.tile_weight(id).width).collect();
+            weights.resize(cols, 1.0);
+
+            let mut x = area.x + gap as i32;
+            for (window_id, width) in row_ids.iter().zip(distribute(usable_width, &weights)) {
+                rects.push((window_id.clone(), Rect { x, y, width, height }));
+                x += width.saturating_add(gap) as i32;
+            }
+
+            y += height.saturating_add(gap) as i32;
+        }
+
+        rects
+    }
+}
+
+/// Columns and rows of the tiling grid for `count` windows
+pub fn tiling_grid(count: usize) -> (usize, usize) {
+    let cols = (count as f64).sqrt().ceil().max(1.0) as usize;
+    let rows = count.div_ceil(cols);
+    (cols, rows)
+}
+
+/// Splits `total` pixels proportionally to `weights`
+fn distribute(total: u32, weights: &[f32]) -> Vec<u32> {
+    let sum: f64 = weights.iter().map(|w| *w as f64).sum();
+    weights
+        .iter()
+        .map(|w| (total as f64 * *w as f64 / sum) as u32)
+        .collect()
+}
+
+pub struct FloatingLayout;
+
+impl Layout for FloatingLayout {
+    fn arrange(&self, _area: Rect, _window_ids: &[String], _gap: u32, _workspace: &Workspace) -> Vec<(String, Rect)> {
+        Vec::new()
+    }
+}
+
+pub struct MonocleLayout;
+
+impl Layout for MonocleLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], _gap: u32, _workspace: &Workspace) -> Vec<(String, Rect)> {
+        window_ids.iter().map(|window_id| (window_id.clone(), area)).collect()
+    }
+}
+
+/// Lays out side-by-side columns whose widths are proportional to their
+/// weights, stacking each column's windows vertically. Empty columns are
+/// dropped so the remaining ones share the full width.
+fn arrange_columns(area: Rect, gap: u32, columns: &[(Vec<String>, f32)]) -> Vec<(String, Rect)> {
+    let columns: Vec<(Vec<String>, f32)> = columns.iter().filter(|(ids, _)| !ids.is_empty()).cloned().collect();
+    arrange_column_slots(area, gap, &columns)
+}
+
+/// Like `arrange_columns`, but empty columns keep their width, leaving a gap
+/// where their windows would go.
+fn arrange_column_slots(area: Rect, gap: u32, columns: &[(Vec<String>, f32)]) -> Vec<(String, Rect)> {
+    if columns.iter().all(|(ids, _)| ids.is_empty()) {
+        return Vec::new();
+    }
+
+    let usable_width = area.width.saturating_sub(gap.saturating_mul(columns.len() as u32 + 1));
+    let total_weight: f32 = columns.iter().map(|(_, weight)| weight).sum();
+
+    let mut rects = Vec::new();
+    let mut x = gap;
+    let mut remaining_width = usable_width;
+
+    for (i, (ids, weight)) in columns.iter().enumerate() {
+        // The last column absorbs rounding so the columns always fill the area
+        let width = if i == columns.len() - 1 {
+            remaining_width
+        } else {
+            ((usable_width as f32 * weight / total_weight) as u32).min(remaining_width)
+        };
+        let rows = ids.len() as u32;
+        let height = area.height.saturating_sub(gap.saturating_mul(rows + 1)) / rows.max(1);
+
+        for (row, window_id) in ids.iter().enumerate() {
+            let rect = Rect {
+                x: area.x + x as i32,
+                y: area.y + gap.saturating_add((row as u32).saturating_mul(height.saturating_add(gap))) as i32,
+                width,
+                height,
+            };
+            rects.push((window_id.clone(), rect));
+        }
+
+        x = x.saturating_add(width.saturating_add(gap));
+        remaining_width -= width;
+    }
+
+    rects
+}
+
+/// Splits windows into left and right stacks, alternating starting on the
+/// right so a single stacked window sits next to the center column.
+fn split_stacks(window_ids: &[String]) -> (Vec<String>, Vec<String>) {
+    let mut left = Vec::new();
+    let mut right = Vec::new();
+
+    for (i, window_id) in window_ids.iter().enumerate() {
+        if i % 2 == 0 {
+            right.push(window_id.clone());
+        } else {
+            left.push(window_id.clone());
+        }
+    }
+
+    (left, right)
+}
+
+pub struct MasterStackLayout;
+
+impl Layout for MasterStackLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let masters = workspace.master_count.min(window_ids.len());
+        let (master, stack) = window_ids.split_at(masters);
+
+        arrange_columns(
+            area,
+            gap,
+            &[
+                (master.to_vec(), workspace.master_ratio),
+                (stack.to_vec(), 1.0 - workspace.master_ratio),
+            ],
+        )
+    }
+}
+
+/// Master windows in a center column sized by the master ratio, with the
+/// remaining windows alternating between a left and a right stack.
+pub struct CenteredMasterLayout;
+
+impl Layout for CenteredMasterLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let masters = workspace.master_count.min(window_ids.len());
+        let (master, stack) = window_ids.split_at(masters);
+        let (left, right) = split_stacks(stack);
+
+        // A lone side stack takes all the width the center column leaves over
+        let center_ratio = workspace.master_ratio;
+        let side_weight = if left.is_empty() || right.is_empty() {
+            1.0 - center_ratio
+        } else {
+            (1.0 - center_ratio) / 2.0
+        };
+
+        arrange_columns(area, gap, &[(left, side_weight), (master.to_vec(), center_ratio), (right, side_weight)])
+    }
+}
+
+/// Three equal columns with the focused window alone in the middle and the
+/// remaining windows alternating between the left and right columns. The
+/// columns keep their place even while a side column is empty.
+pub struct ThreeColumnLayout;
+
+impl Layout for ThreeColumnLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let center = match &workspace.focused_window {
+            Some(focused) if window_ids.contains(focused) => focused.clone(),
+            _ => match window_ids.first() {
+                Some(first) => first.clone(),
+                None => return Vec::new(),
+            },
+        };
+        let stack: Vec<String> = window_ids.iter().filter(|id| **id != center).cloned().collect();
+        let (left, right) = split_stacks(&stack);
+
+        arrange_column_slots(area, gap, &[(left, 1.0), (vec![center], 1.0), (right, 1.0)])
+    }
+
+    fn depends_on_focus(&self) -> bool {
+        true
+    }
+}
+
+pub struct BspLayout;
+
+impl Layout for BspLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let inner = Rect {
+            x: area.x + gap as i32,
+            y: area.y + gap as i32,
+            width: area.width.saturating_sub(gap.saturating_mul(2)),
+            height: area.height.saturating_sub(gap.saturating_mul(2)),
+        };
+
+        match &workspace.bsp_tree {
+            Some(tree) => tree
+                .layout(inner, gap)
+                .into_iter()
+                .filter(|(window_id, _)| window_ids.contains(window_id))
+                .collect(),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn header_height(workspace: &Workspace) -> u32 {
+    workspace
+        .layout_options
+        .get("header_height")
+        .and_then(|value| value.as_u64())
+        .map(|height| height as u32)
+        .unwrap_or(DEFAULT_TAB_HEADER_HEIGHT)
+}
+
+/// Header strips drawn above windows in the tabbed and stacked layouts, in
+/// window order. Other layouts have no headers.
+pub fn tab_headers(area: Rect, window_ids: &[String], workspace: &Workspace) -> Vec<(String, Rect)> {
+    let height = header_height(workspace);
+    let count = window_ids.len() as u32;
+
+    match workspace.layout {
+        LayoutType::Tabbed if count > 0 => {
+            let tab_width = area.width / count;
+            window_ids
+                .iter()
+                .enumerate()
+                .map(|(i, window_id)| {
+                    let rect = Rect {
+                        x: area.x + (i as u32).saturating_mul(tab_width) as i32,
+                        y: area.y,
+                        width: tab_width,
+                        height,
+                    };
+                    (window_id.clone(), rect)
+                })
+                .collect()
+        }
+        LayoutType::Stacked => window_ids
+            .iter()
+            .enumerate()
+            .map(|(i, window_id)| {
+                let rect = Rect {
+                    y: area.y + (i as u32).saturating_mul(height) as i32,
+                    height,
+                    ..area
+                };
+                (window_id.clone(), rect)
+            })
+            .collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// Every window shares the area below a single row of tabs
+pub struct TabbedLayout;
+
+impl Layout for TabbedLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], _gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let top = header_height(workspace);
+        let rect = Rect {
+            y: area.y + top.min(area.height) as i32,
+            height: area.height.saturating_sub(top),
+            ..area
+        };
+
+        window_ids.iter().map(|window_id| (window_id.clone(), rect)).collect()
+    }
+}
+
+/// Every window shares the area below one title strip per window
+pub struct StackedLayout;
+
+impl Layout for StackedLayout {
+    fn arrange(&self, area: Rect, window_ids: &[String], _gap: u32, workspace: &Workspace) -> Vec<(String, Rect)> {
+        let top = header_height(workspace).saturating_mul(window_ids.len() as u32);
+        let rect = Rect {
+            y: area.y + top.min(area.height) as i32,
+            height: area.height.saturating_sub(top),
+            ..area
+        };
+
+        window_ids.iter().map(|window_id| (window_id.clone(), rect)).collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{MAX_MASTER_RATIO, MIN_MASTER_RATIO};
+
+    #[test]
+    fn layouts_fit_areas_smaller_than_their_gaps() {
+        let layouts: Vec<(LayoutType, Box<dyn Layout>)> = vec![
+            (LayoutType::Tiling, Box::new(TilingLayout)),
+            (LayoutType::Monocle, Box::new(MonocleLayout)),
+            (LayoutType::MasterStack, Box::new(MasterStackLayout)),
+            (LayoutType::Bsp, Box::new(BspLayout)),
+            (LayoutType::CenteredMaster, Box::new(CenteredMasterLayout)),
+            (LayoutType::ThreeColumn, Box::new(ThreeColumnLayout)),
+            (LayoutType::Tabbed, Box::new(TabbedLayout)),
+            (LayoutType::Stacked, Box::new(StackedLayout)),
+        ];
+        let areas = [
+            Rect { x: 0, y: 0, width: 0, height: 0 },
+            Rect { x: 0, y: 0, width: 1920, height: 1080 },
+        ];
+
+        for (layout_type, layout) in &layouts {
+            let mut workspace = Workspace::new("Test".to_string(), layout_type.clone());
+            for i in 0..200 {
+                workspace.add_window(format!("window-{}", i));
+            }
+            for area in areas {
+                for rect in layout.arrange(area, workspace.get_windows(), 10, &workspace).into_iter().map(|(_, rect)| rect) {
+                    assert!(rect.width <= area.width && rect.height <= area.height, "{} overflowed: {:?}", layout_type, rect);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn neighbor_prefers_windows_overlapping_on_the_other_axis() {
+        let from = Rect { x: 0, y: 0, width: 100, height: 100 };
+        let candidates = vec![
+            // Closer, but only diagonally to the right
+            ("diagonal".to_string(), Rect { x: 100, y: 150, width: 100, height: 100 }),
+            ("beside".to_string(), Rect { x: 300, y: 50, width: 100, height: 100 }),
+            ("below".to_string(), Rect { x: 0, y: 200, width: 100, height: 100 }),
+        ];
+
+        assert_eq!(neighbor_in_direction(from, &candidates, Direction::Right).as_deref(), Some("beside"));
+        assert_eq!(neighbor_in_direction(from, &candidates, Direction::Down).as_deref(), Some("below"));
+        assert_eq!(neighbor_in_direction(from, &candidates, Direction::Left), None);
+        assert_eq!(neighbor_in_direction(from, &candidates, Direction::Up), None);
+    }
+
+    fn master_stack(master_ratio: f32, master_count: usize) -> Workspace {
+        let mut workspace = Workspace::new("Master".to_string(), LayoutType::MasterStack);
+        workspace.master_ratio = master_ratio;
+        workspace.master_count = master_count;
+        workspace
+    }
+
+    #[test]
+    fn master_column_width_follows_the_ratio() {
+        let area = Rect { x: 0, y: 0, width: 1010, height: 600 };
+        let ids: Vec<String> = ["a", "b"].map(String::from).to_vec();
+
+        assert_eq!(MasterStackLayout.arrange(area, &ids, 10, &master_stack(0.75, 1)), [
+            ("a".to_string(), Rect { x: 10, y: 10, width: 735, height: 580 }),
+            ("b".to_string(), Rect { x: 755, y: 10, width: 245, height: 580 }),
+        ]);
+    }
+
+    #[test]
+    fn several_masters_stack_vertically() {
+        let area = Rect { x: 0, y: 0, width: 1010, height: 600 };
+        let ids: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+
+        assert_eq!(MasterStackLayout.arrange(area, &ids, 10, &master_stack(0.75, 2)), [
+            ("a".to_string(), Rect { x: 10, y: 10, width: 735, height: 285 }),
+            ("b".to_string(), Rect { x: 10, y: 305, width: 735, height: 285 }),
+            ("c".to_string(), Rect { x: 755, y: 10, width: 245, height: 580 }),
+        ]);
+    }
+
+    #[test]
+    fn a_lone_master_or_stack_column_takes_the_full_width() {
+        let area = Rect { x: 0, y: 0, width: 1010, height: 600 };
+        let ids: Vec<String> = ["a", "b"].map(String::from).to_vec();
+        let full_width = [
+            ("a".to_string(), Rect { x: 10, y: 10, width: 990, height: 285 }),
+            ("b".to_string(), Rect { x: 10, y: 305, width: 990, height: 285 }),
+        ];
+
+        assert_eq!(MasterStackLayout.arrange(area, &ids, 10, &master_stack(0.75, 0)), full_width);
+        assert_eq!(MasterStackLayout.arrange(area, &ids, 10, &master_stack(0.75, 2)), full_width);
+        assert_eq!(MasterStackLayout.arrange(area, &ids, 10, &master_stack(0.75, 5)), full_width);
+    }
+
+    #[test]
+    fn master_ratio_and_count_stay_in_range() {
+        let mut workspace = master_stack(0.5, 1);
+        workspace.adjust_master_ratio(1.0);
+        assert_eq!(workspace.master_ratio, MAX_MASTER_RATIO);
+        workspace.adjust_master_ratio(-5.0);
+        assert_eq!(workspace.master_ratio, MIN_MASTER_RATIO);
+
+        workspace.adjust_master_count(i32::MAX);
+        assert_eq!(workspace.master_count, 1 + i32::MAX as usize);
+        workspace.adjust_master_count(i32::MIN);
+        assert_eq!(workspace.master_count, 0);
+        workspace.adjust_master_count(-1);
+        assert_eq!(workspace.master_count, 0);
+    }
+
+    #[test]
+    fn centered_master_splits_an_odd_stack_towards_the_right() {
+        let area = Rect { x: 0, y: 0, width: 1040, height: 600 };
+        let ids: Vec<String> = ["a", "b", "c", "d"].map(String::from).to_vec();
+        let mut workspace = Workspace::new("Centered".to_string(), LayoutType::CenteredMaster);
+        workspace.master_ratio = 0.5;
+
+        assert_eq!(CenteredMasterLayout.arrange(area, &ids, 10, &workspace), [
+            ("c".to_string(), Rect { x: 10, y: 10, width: 250, height: 580 }),
+            ("a".to_string(), Rect { x: 270, y: 10, width: 500, height: 580 }),
+            ("b".to_string(), Rect { x: 780, y: 10, width: 250, height: 285 }),
+            ("d".to_string(), Rect { x: 780, y: 305, width: 250, height: 285 }),
+        ]);
+    }
+
+    #[test]
+    fn centered_master_splits_an_even_stack_evenly() {
+        let area = Rect { x: 0, y: 0, width: 1040, height: 600 };
+        let ids: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+        let mut workspace = Workspace::new("Centered".to_string(), LayoutType::CenteredMaster);
+        workspace.master_ratio = 0.5;
+
+        assert_eq!(CenteredMasterLayout.arrange(area, &ids, 10, &workspace), [
+            ("c".to_string(), Rect { x: 10, y: 10, width: 250, height: 580 }),
+            ("a".to_string(), Rect { x: 270, y: 10, width: 500, height: 580 }),
+            ("b".to_string(), Rect { x: 780, y: 10, width: 250, height: 580 }),
+        ]);
+    }
+
+    #[test]
+    fn centered_master_gives_a_lone_stack_window_the_rest() {
+        let area = Rect { x: 0, y: 0, width: 1040, height: 600 };
+        let ids: Vec<String> = ["a", "b"].map(String::from).to_vec();
+        let mut workspace = Workspace::new("Centered".to_string(), LayoutType::CenteredMaster);
+        workspace.master_ratio = 0.5;
+
+        assert_eq!(CenteredMasterLayout.arrange(area, &ids, 10, &workspace), [
+            ("a".to_string(), Rect { x: 10, y: 10, width: 505, height: 580 }),
+            ("b".to_string(), Rect { x: 525, y: 10, width: 505, height: 580 }),
+        ]);
+    }
+
+    #[test]
+    fn three_columns_keep_the_focused_window_in_the_middle() {
+        let area = Rect { x: 0, y: 0, width: 1040, height: 600 };
+        let ids: Vec<String> = ["a", "b"].map(String::from).to_vec();
+        let mut workspace = Workspace::new("Columns".to_string(), LayoutType::ThreeColumn);
+        workspace.focused_window = Some("a".to_string());
+
+        assert_eq!(ThreeColumnLayout.arrange(area, &ids, 10, &workspace), [
+            ("a".to_string(), Rect { x: 353, y: 10, width: 333, height: 580 }),
+            ("b".to_string(), Rect { x: 696, y: 10, width: 334, height: 580 }),
+        ]);
+    }
+
+    fn workspace_with(layout: LayoutType, header_height: Option<u32>) -> Workspace {
+        let mut workspace = Workspace::new("Tabs".to_string(), layout);
+        if let Some(height) = header_height {
+            workspace.layout_options.insert("header_height".to_string(), height.into());
         }
+        workspace
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn tabbed_headers_share_one_row() {
+        let area = Rect { x: 100, y: 50, width: 900, height: 600 };
+        let ids: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+        let workspace = workspace_with(LayoutType::Tabbed, None);
+
+        let headers: Vec<Rect> = tab_headers(area, &ids, &workspace).into_iter().map(|(_, rect)| rect).collect();
+        assert_eq!(headers, [
+            Rect { x: 100, y: 50, width: 300, height: DEFAULT_TAB_HEADER_HEIGHT },
+            Rect { x: 400, y: 50, width: 300, height: DEFAULT_TAB_HEADER_HEIGHT },
+            Rect { x: 700, y: 50, width: 300, height: DEFAULT_TAB_HEADER_HEIGHT },
+        ]);
+
+        let below = Rect { y: 50 + DEFAULT_TAB_HEADER_HEIGHT as i32, height: 600 - DEFAULT_TAB_HEADER_HEIGHT, ..area };
+        assert!(TabbedLayout.arrange(area, &ids, 10, &workspace).iter().all(|(_, rect)| *rect == below));
+    }
+
+    #[test]
+    fn stacked_headers_use_the_configured_height() {
+        let area = Rect { x: 0, y: 0, width: 800, height: 600 };
+        let ids: Vec<String> = ["a", "b", "c"].map(String::from).to_vec();
+        let workspace = workspace_with(LayoutType::Stacked, Some(30));
+
+        let headers = tab_headers(area, &ids, &workspace);
+        assert_eq!(headers.iter().map(|(id, _)| id.as_str()).collect::<Vec<_>>(), ["a", "b", "c"]);
+        assert_eq!(headers.iter().map(|(_, rect)| rect.y).collect::<Vec<_>>(), [0, 30, 60]);
+        assert!(headers.iter().all(|(_, rect)| rect.width == 800 && rect.height == 30));
+
+        let below = Rect { y: 90, height: 510, ..area };
+        assert!(StackedLayout.arrange(area, &ids, 10, &workspace).iter().all(|(_, rect)| *rect == below));
+    }
+
+    #[test]
+    fn other_layouts_have_no_headers() {
+        let area = Rect { x: 0, y: 0, width: 800, height: 600 };
+        let ids = vec!["a".to_string()];
+        assert!(tab_headers(area, &ids, &workspace_with(LayoutType::Tiling, None)).is_empty());
+        assert!(tab_headers(area, &[], &workspace_with(LayoutType::Tabbed, None)).is_empty());
+    }
+}
@@ -6,18 +6,36 @@
 pub mod window_manager;
 pub mod workspace;
 pub mod layout;
+pub mod bsp;
+pub mod scratchpad;
 pub mod config;
+pub mod rules;
+pub mod events;
+pub mod hooks;
+pub mod monitor;
 pub mod commands;
 pub mod system_window;
 
-pub use window_manager::{WindowManager, ManagedWindow, WindowPosition, WindowSize, WindowState};
-pub use workspace::Workspace;
-pub use layout::LayoutType;
+pub use window_manager::{EventSink, WindowManager, ManagedWindow, WindowPosition, WindowSize, WindowState};
+pub use workspace::{OrphanPolicy, Workspace};
+pub use layout::{Direction, Layout, LayoutType, Rect, TabInfo, TileWeight};
+pub use bsp::{BspNode, SplitDirection};
+pub use scratchpad::Scratchpad;
 pub use config::{Config, KeyBindings};
+pub use rules::{MatchType, RuleActions, WindowRule};
+pub use hooks::{HookResult, Hooks, WindowAdd};
+pub use monitor::{Monitor, ScreenStrut, Strut};
 pub use commands::*;
-pub use system_window::{SystemWindow, SystemWindowManager, PlatformWindowManager};
+pub use system_window::{
+    SystemWindow, SystemWindowEvent, SystemWindowManager, PlatformWindowManager, WindowBounds, WindowStateFlag,
+    SYSTEM_WINDOW_EVENT,
+};
+#[cfg(target_os = "linux")]
+pub use system_window::X11Backend;
 
-use tauri::{Manager, AppHandle, State, plugin::TauriPlugin};
+use tauri::{Manager, State, plugin::TauriPlugin};
+#[cfg(target_os = "linux")]
+use tauri::{AppHandle, Emitter};
 
 /// Plugin init function (what consumers call from their Tauri app)
 pub fn init<R: tauri::Runtime>() -> TauriPlugin<R> {
@@ -26,16 +44,48 @@ pub fn init<R: tauri::Runtime>() -> TauriPlugin<R> {
             get_windows,
             create_workspace,
             switch_workspace,
+            switch_workspace_by_number,
+            rename_workspace,
+            move_workspace,
+            delete_workspace,
             get_workspaces,
+            get_workspace_by_name,
+            get_layouts,
+            get_tabs,
+            set_layout_option,
             arrange_windows,
+            adjust_master_ratio,
+            adjust_master_count,
+            resize_bsp_split,
+            rotate_bsp_split,
+            preselect_bsp_split,
+            resize_tile,
+            reset_tile_weights,
             close_window,
             minimize_window,
             maximize_window,
             focus_window,
+            focus_next,
+            focus_prev,
+            focus_direction,
+            swap_direction,
+            get_monitors,
+            set_workspace_monitor,
+            reserve_strut,
             get_config,
             update_config,
             add_window_to_manager,
+            manage_system_window,
             remove_window_from_manager,
+            move_window_to_workspace,
+            toggle_sticky,
+            toggle_floating,
+            center_floating_window,
+            send_to_scratchpad,
+            send_system_window_to_scratchpad,
+            remove_system_window_from_scratchpad,
+            toggle_scratchpad,
+            get_scratchpad,
             get_system_windows,
             move_system_window,
             resize_system_window,
@@ -43,15 +93,57 @@ pub fn init<R: tauri::Runtime>() -> TauriPlugin<R> {
             minimize_system_window,
             maximize_system_window,
             restore_system_window,
+            set_system_window_state,
             close_system_window,
             focus_system_window,
             hide_system_window,
             show_system_window,
-            arrange_system_windows
+            arrange_system_windows,
+            arrange_system_windows_with_layout,
+            get_system_window_tabs
         ])
         .setup(|app_handle, _| {
-            app_handle.manage(WindowManager::new());
+            let window_manager = WindowManager::new();
+            window_manager.set_app_handle(app_handle.clone());
+            app_handle.manage(window_manager);
+            #[cfg(target_os = "linux")]
+            forward_system_window_events(app_handle.clone());
             Ok(())
         })
         .build()
 }
+
+/// Re-emits native window changes to the frontend as `SYSTEM_WINDOW_EVENT`
+#[cfg(target_os = "linux")]
+fn forward_system_window_events<R: tauri::Runtime>(app_handle: AppHandle<R>) {
+    let events = match X11Backend::global().and_then(|backend| backend.subscribe()) {
+        Ok(events) => events,
+        Err(e) => {
+            log::warn!("System window events are unavailable: {}", e);
+            return;
+        }
+    };
+
+    std::thread::spawn(move || {
+        for event in events {
+            let wm = app_handle.state::<WindowManager>();
+            match event {
+                SystemWindowEvent::WorkAreaChanged => {
+                    if let Err(e) = wm.refresh_work_area() {
+                        log::warn!("Failed to re-arrange for the new work area: {}", e);
+                    }
+                }
+                // Panels and docks come and go like any other window
+                SystemWindowEvent::Created { .. }
+                | SystemWindowEvent::Destroyed { .. }
+                | SystemWindowEvent::Mapped { .. }
+                | SystemWindowEvent::Unmapped { .. } => wm.invalidate_monitors(),
+                _ => {}
+            }
+
+            if let Err(e) = app_handle.emit(SYSTEM_WINDOW_EVENT, &event) {
+                log::warn!("Failed to emit system window event: {}", e);
+            }
+        }
+    });
+}
@@ -0,0 +1,223 @@
+use serde::{Deserialize, Serialize};
+use crate::config::Config;
+use crate::layout::Rect;
+
+/// Id of the stand-in monitor used when the platform reports none
+pub const DEFAULT_MONITOR_ID: &str = "default";
+
+/// Pixel density that corresponds to a scale of 1.0
+const BASE_DPI: f32 = 96.0;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Monitor {
+    /// Identifier workspaces are bound by; the RandR output name on Linux
+    pub id: String,
+    pub name: String,
+    /// Position and size in the global screen coordinate space
+    pub geometry: Rect,
+    /// Part of `geometry` not covered by panels and docks
+    pub work_area: Rect,
+    pub primary: bool,
+    /// Physical pixels per logical pixel
+    pub scale: f32,
+}
+
+impl Monitor {
+    /// A single monitor sized from `Config::screen_width`/`screen_height`, for
+    /// platforms where monitors cannot be enumerated
+    pub fn from_config(config: &Config) -> Self {
+        let geometry = Rect {
+            x: 0,
+            y: 0,
+            width: config.screen_width,
+            height: config.screen_height,
+        };
+
+        Self {
+            id: DEFAULT_MONITOR_ID.to_string(),
+            name: "Default".to_string(),
+            geometry,
+            work_area: geometry,
+            primary: true,
+            scale: 1.0,
+        }
+    }
+
+    /// Shrinks the work area by a strut reserved at the edges of the whole
+    /// screen, if the strut lies on this monitor. A strut along an edge shared
+    /// with another monitor only affects the monitor it covers.
+    pub fn reserve_screen_strut(&mut self, strut: &ScreenStrut, screen_width: u32, screen_height: u32) {
+        let geometry = self.geometry;
+        let (mon_left, mon_top) = (geometry.x as i64, geometry.y as i64);
+        let (mon_right, mon_bottom) = (mon_left + geometry.width as i64, mon_top + geometry.height as i64);
+        let overlaps = |start: i64, end: i64, from: i64, to: i64| start < to && from < end;
+
+        let mut left = self.work_area.x as i64;
+        let mut top = self.work_area.y as i64;
+        let mut right = left + self.work_area.width as i64;
+        let mut bottom = top + self.work_area.height as i64;
+
+        let (screen_width, screen_height) = (screen_width as i64, screen_height as i64);
+
+        if strut.left > 0
+            && overlaps(0, strut.left as i64, mon_left, mon_right)
+            && overlaps(strut.left_start_y as i64, strut.left_end_y as i64 + 1, mon_top, mon_bottom)
+        {
+            left = left.max(strut.left as i64);
+        }
+        if strut.right > 0
+            && overlaps(screen_width - strut.right as i64, screen_width, mon_left, mon_right)
+            && overlaps(strut.right_start_y as i64, strut.right_end_y as i64 + 1, mon_top, mon_bottom)
+        {
+            right = right.min(screen_width - strut.right as i64);
+        }
+        if strut.top > 0
+            && overlaps(0, strut.top as i64, mon_top, mon_bottom)
+            && overlaps(strut.top_start_x as i64, strut.top_end_x as i64 + 1, mon_left, mon_right)
+        {
+            top = top.max(strut.top as i64);
+        }
+        if strut.bottom > 0
+            && overlaps(screen_height - strut.bottom as i64, screen_height, mon_top, mon_bottom)
+            && overlaps(strut.bottom_start_x as i64, strut.bottom_end_x as i64 + 1, mon_left, mon_right)
+        {
+            bottom = bottom.min(screen_height - strut.bottom as i64);
+        }
+
+        self.work_area = Rect {
+            x: left as i32,
+            y: top as i32,
+            width: (right - left).max(0) as u32,
+            height: (bottom - top).max(0) as u32,
+        };
+    }
+
+    /// Estimates the scale from the physical width, rounded to quarter steps.
+    /// Without a physical size (e.g. virtual outputs) the scale is 1.0.
+    pub fn scale_for_size(width_px: u32, width_mm: u64) -> f32 {
+        if width_mm == 0 {
+            return 1.0;
+        }
+        let dpi = width_px as f32 / (width_mm as f32 / 25.4);
+        ((dpi / BASE_DPI * 4.0).round() / 4.0).max(1.0)
+    }
+}
+
+/// Space reserved along the edges of the whole screen, as described by
+/// `_NET_WM_STRUT_PARTIAL`: the thickness of each edge and the range along
+/// that edge it spans (inclusive)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct ScreenStrut {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+    pub left_start_y: u32,
+    pub left_end_y: u32,
+    pub right_start_y: u32,
+    pub right_end_y: u32,
+    pub top_start_x: u32,
+    pub top_end_x: u32,
+    pub bottom_start_x: u32,
+    pub bottom_end_x: u32,
+}
+
+/// Space the app keeps free at the edges of a monitor's work area, e.g. for a
+/// bar it renders itself
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct Strut {
+    pub left: u32,
+    pub right: u32,
+    pub top: u32,
+    pub bottom: u32,
+}
+
+impl Strut {
+    pub fn is_empty(&self) -> bool {
+        *self == Strut::default()
+    }
+
+    /// `area` with the strut taken off its edges
+    pub fn apply(&self, area: Rect) -> Rect {
+        let left = self.left.min(area.width);
+        let top = self.top.min(area.height);
+        Rect {
+            x: area.x + left as i32,
+            y: area.y + top as i32,
+            width: area.width.saturating_sub(self.left.saturating_add(self.right)),
+            height: area.height.saturating_sub(self.top.saturating_add(self.bottom)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn monitor(id: &str, x: i32, width: u32, height: u32) -> Monitor {
+        let geometry = Rect { x, y: 0, width, height };
+        Monitor {
+            id: id.to_string(),
+            name: id.to_string(),
+            geometry,
+            work_area: geometry,
+            primary: x == 0,
+            scale: 1.0,
+        }
+    }
+
+    #[test]
+    fn screen_struts_only_shrink_the_monitors_they_cover() {
+        // A 1080p monitor next to a taller 1440p one, on a 4480x1440 screen
+        let (screen_width, screen_height) = (4480, 1440);
+        let mut left = monitor("DP-1", 0, 1920, 1080);
+        let mut right = monitor("DP-2", 1920, 2560, 1440);
+
+        // A panel along the top of the left monitor and a dock on the right edge
+        let panel = ScreenStrut { top: 30, top_start_x: 0, top_end_x: 1919, ..Default::default() };
+        let dock = ScreenStrut { right: 50, right_start_y: 0, right_end_y: 1439, ..Default::default() };
+        for strut in [&panel, &dock] {
+            left.reserve_screen_strut(strut, screen_width, screen_height);
+            right.reserve_screen_strut(strut, screen_width, screen_height);
+        }
+
+        assert_eq!(left.work_area, Rect { x: 0, y: 30, width: 1920, height: 1050 });
+        assert_eq!(right.work_area, Rect { x: 1920, y: 0, width: 2510, height: 1440 });
+    }
+
+    #[test]
+    fn bottom_strut_of_a_shorter_monitor_counts_from_the_screen_edge() {
+        let mut left = monitor("DP-1", 0, 1920, 1080);
+        let mut right = monitor("DP-2", 1920, 2560, 1440);
+
+        // A 40px bar at the bottom of the left monitor, 360px above the screen's bottom edge
+        let bar = ScreenStrut { bottom: 400, bottom_start_x: 0, bottom_end_x: 1919, ..Default::default() };
+        left.reserve_screen_strut(&bar, 4480, 1440);
+        right.reserve_screen_strut(&bar, 4480, 1440);
+
+        assert_eq!(left.work_area.height, 1040);
+        assert_eq!(right.work_area, right.geometry);
+    }
+
+    #[test]
+    fn scale_is_rounded_to_quarter_steps() {
+        // A 27" 4K panel is about 163 DPI
+        assert_eq!(Monitor::scale_for_size(3840, 600), 1.75);
+        assert_eq!(Monitor::scale_for_size(1920, 530), 1.0);
+        // Low densities and unknown sizes never scale down
+        assert_eq!(Monitor::scale_for_size(1024, 1000), 1.0);
+        assert_eq!(Monitor::scale_for_size(3840, 0), 1.0);
+    }
+
+    #[test]
+    fn strut_larger_than_the_area_leaves_it_empty() {
+        let area = Rect { x: 100, y: 50, width: 800, height: 600 };
+        let strut = Strut { left: u32::MAX, right: u32::MAX, top: 20, bottom: u32::MAX };
+
+        assert_eq!(strut.apply(area), Rect { x: 900, y: 70, width: 0, height: 0 });
+        assert_eq!(
+            Strut { left: 10, right: 20, top: 30, bottom: 40 }.apply(area),
+            Rect { x: 110, y: 80, width: 770, height: 530 },
+        );
+    }
+}
@@ -0,0 +1,232 @@
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+
+/// How the patterns of a `WindowRule` are interpreted
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub enum MatchType {
+    /// `*` matches any run of characters and `?` any single character
+    #[default]
+    Glob,
+    Regex,
+}
+
+/// Actions applied to a window matched by a rule. Unset fields leave the
+/// window untouched; when several rules match, later rules win.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct RuleActions {
+    /// Workspace id or name the window is placed on
+    pub workspace: Option<String>,
+    pub floating: Option<bool>,
+    pub width: Option<u32>,
+    pub height: Option<u32>,
+    pub sticky: Option<bool>,
+    /// Leave the window out of tiling without moving it
+    pub ignore: Option<bool>,
+    pub start_minimized: Option<bool>,
+    /// Marks the window as a terminal whose GUI children swallow it
+    pub terminal: Option<bool>,
+}
+
+impl RuleActions {
+    fn merge(&mut self, other: &RuleActions) {
+        if other.workspace.is_some() {
+            self.workspace = other.workspace.clone();
+        }
+        self.floating = other.floating.or(self.floating);
+        self.width = other.width.or(self.width);
+        self.height = other.height.or(self.height);
+        self.sticky = other.sticky.or(self.sticky);
+        self.ignore = other.ignore.or(self.ignore);
+        self.start_minimized = other.start_minimized.or(self.start_minimized);
+        self.terminal = other.terminal.or(self.terminal);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == RuleActions::default()
+    }
+}
+
+/// Matches windows by app name, process name and title. Every pattern that is
+/// set must match; a rule without patterns matches every window.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct WindowRule {
+    pub app_name: Option<String>,
+    pub process_name: Option<String>,
+    pub title: Option<String>,
+    #[serde(default)]
+    pub match_type: MatchType,
+    #[serde(default)]
+    pub actions: RuleActions,
+    /// Filled in by `compile` when rules are loaded
+    #[serde(skip)]
+    pub(crate) compiled: CompiledPatterns,
+}
+
+/// Regexes compiled from the patterns of a `WindowRule`. Rules are evaluated
+/// for every new window, so they are compiled once, when the config is loaded.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct CompiledPatterns {
+    app_name: Option<CompiledPattern>,
+    process_name: Option<CompiledPattern>,
+    title: Option<CompiledPattern>,
+}
+
+/// A regex along with the pattern it was compiled from, which tells whether
+/// the pattern changed since. Invalid patterns are kept as `None`, so they
+/// are logged once and never match.
+#[derive(Debug, Clone)]
+struct CompiledPattern {
+    pattern: String,
+    regex: Option<Regex>,
+}
+
+impl CompiledPattern {
+    fn new(pattern: &str) -> Self {
+        Self { pattern: pattern.to_string(), regex: compile_regex(pattern) }
+    }
+}
+
+fn compile_regex(pattern: &str) -> Option<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| log::warn!("Ignoring invalid window rule regex '{}': {}", pattern, e))
+        .ok()
+}
+
+impl WindowRule {
+    /// Compiles the regexes of a `MatchType::Regex` rule, replacing those of
+    /// earlier patterns
+    pub fn compile(&mut self) {
+        let compile = |pattern: &Option<String>| match self.match_type {
+            MatchType::Regex => pattern.as_deref().map(CompiledPattern::new),
+            MatchType::Glob => None,
+        };
+        let compiled = CompiledPatterns {
+            app_name: compile(&self.app_name),
+            process_name: compile(&self.process_name),
+            title: compile(&self.title),
+        };
+        self.compiled = compiled;
+    }
+
+    /// `process_name` is `None` for managed windows, which never match a rule
+    /// that requires one
+    pub fn matches(&self, app_name: Option<&str>, process_name: Option<&str>, title: &str) -> bool {
+        self.field_matches(&self.app_name, &self.compiled.app_name, app_name)
+            && self.field_matches(&self.process_name, &self.compiled.process_name, process_name)
+            && self.field_matches(&self.title, &self.compiled.title, Some(title))
+    }
+
+    fn field_matches(&self, pattern: &Option<String>, compiled: &Option<CompiledPattern>, value: Option<&str>) -> bool {
+        match (pattern, value) {
+            (None, _) => true,
+            (Some(_), None) => false,
+            (Some(pattern), Some(value)) => match self.match_type {
+                MatchType::Glob => glob_matches(pattern, value),
+                MatchType::Regex => match compiled.as_ref().filter(|compiled| compiled.pattern == *pattern) {
+                    Some(compiled) => compiled.regex.as_ref().is_some_and(|regex| regex.is_match(value)),
+                    // Not compiled since the pattern was set, so only for this match
+                    None => compile_regex(pattern).is_some_and(|regex| regex.is_match(value)),
+                },
+            },
+        }
+    }
+}
+
+/// Combined actions of every rule matching the window
+pub fn evaluate_rules(rules: &[WindowRule], app_name: Option<&str>, process_name: Option<&str>, title: &str) -> RuleActions {
+    let mut actions = RuleActions::default();
+    for rule in rules.iter().filter(|rule| rule.matches(app_name, process_name, title)) {
+        actions.merge(&rule.actions);
+    }
+    actions
+}
+
+fn glob_matches(pattern: &str, value: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let value: Vec<char> = value.chars().collect();
+
+    // Iterative matcher that backtracks to the most recent `*`
+    let (mut p, mut v) = (0, 0);
+    let mut star: Option<(usize, usize)> = None;
+
+    while v < value.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == value[v]) {
+            p += 1;
+            v += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            star = Some((p, v));
+            p += 1;
+        } else if let Some((star_p, star_v)) = star {
+            p = star_p + 1;
+            v = star_v + 1;
+            star = Some((star_p, star_v + 1));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+    use crate::window_manager::WindowManager;
+
+    fn regex_rule(title: &str) -> WindowRule {
+        WindowRule {
+            title: Some(title.to_string()),
+            match_type: MatchType::Regex,
+            actions: RuleActions { floating: Some(true), ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn loaded_rules_are_compiled() {
+        let wm = WindowManager::new();
+        let glob = WindowRule { title: Some("Picture-in-*".to_string()), ..Default::default() };
+        let config = Config { rules: vec![regex_rule("^Picture-in-Picture$"), glob], ..Default::default() };
+        wm.update_config(config);
+
+        let config = wm.config.lock().unwrap();
+        let compiled = config.rules[0].compiled.title.as_ref().unwrap();
+        assert_eq!(compiled.pattern, "^Picture-in-Picture$");
+        assert!(compiled.regex.as_ref().is_some_and(|regex| regex.is_match("Picture-in-Picture")));
+        assert!(config.rules[0].compiled.app_name.is_none());
+        // Globs are matched as they are
+        assert!(config.rules[1].compiled.title.is_none());
+
+        assert!(config.rules[0].matches(Some("firefox"), None, "Picture-in-Picture"));
+        assert!(!config.rules[0].matches(Some("firefox"), None, "Mozilla Firefox"));
+    }
+
+    #[test]
+    fn changed_patterns_take_effect() {
+        let mut rule = regex_rule("^Steam$");
+        rule.compile();
+        assert!(rule.matches(None, None, "Steam"));
+
+        rule.title = Some("^Friends List$".to_string());
+        assert!(!rule.matches(None, None, "Steam"));
+        assert!(rule.matches(None, None, "Friends List"));
+    }
+
+    #[test]
+    fn invalid_regex_never_matches() {
+        let mut rule = regex_rule("(unclosed");
+        rule.compile();
+        assert!(!rule.matches(Some("app"), None, "(unclosed"));
+        assert!(evaluate_rules(&[rule], Some("app"), None, "(unclosed").is_empty());
+    }
+
+    #[test]
+    fn config_without_rules_deserializes() {
+        let mut config = serde_json::to_value(Config::default()).unwrap();
+        config.as_object_mut().unwrap().remove("rules");
+
+        let config: Config = serde_json::from_value(config).unwrap();
+        assert!(config.rules.is_empty());
+    }
+}
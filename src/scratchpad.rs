@@ -0,0 +1,68 @@
+use serde::{Deserialize, Serialize};
+use crate::layout::LayoutType;
+use crate::workspace::Workspace;
+
+/// `workspace_id` given to managed windows parked on the scratchpad
+pub const SCRATCHPAD_WORKSPACE_ID: &str = "scratchpad";
+
+/// Fraction of the screen width and height a summoned scratchpad window covers
+pub const SCRATCHPAD_SIZE_RATIO: f32 = 0.6;
+
+/// Hidden holding area for windows that can be summoned on top of the active
+/// workspace. Managed windows live in `workspace`, system windows are tracked
+/// by handle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Scratchpad {
+    pub workspace: Workspace,
+    pub system_windows: Vec<u64>,
+    pub visible: bool,
+}
+
+impl Scratchpad {
+    pub fn new() -> Self {
+        let mut workspace = Workspace::new("Scratchpad".to_string(), LayoutType::Floating);
+        workspace.id = SCRATCHPAD_WORKSPACE_ID.to_string();
+
+        Self {
+            workspace,
+            system_windows: Vec::new(),
+            visible: false,
+        }
+    }
+
+    pub fn add_system_window(&mut self, handle: u64) {
+        if !self.system_windows.contains(&handle) {
+            self.system_windows.push(handle);
+        }
+    }
+
+    /// Takes a managed window off the scratchpad; an emptied scratchpad is
+    /// hidden, so the next window sent to it starts out hidden too
+    pub fn remove_window(&mut self, window_id: &str) {
+        self.workspace.remove_window(window_id);
+        self.hide_if_empty();
+    }
+
+    pub fn remove_system_window(&mut self, handle: u64) -> bool {
+        let before = self.system_windows.len();
+        self.system_windows.retain(|h| *h != handle);
+        self.hide_if_empty();
+        self.system_windows.len() != before
+    }
+
+    fn hide_if_empty(&mut self) {
+        if self.is_empty() {
+            self.visible = false;
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.workspace.windows.is_empty() && self.system_windows.is_empty()
+    }
+}
+
+impl Default for Scratchpad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -1,10 +1,15 @@
 use serde::{Deserialize, Serialize};
+use crate::monitor::Monitor;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SystemWindow {
     pub handle: u64,
     pub title: String,
     pub process_name: String,
+    /// Window class (`WM_CLASS` on X11); empty where the platform has none
+    pub class: String,
+    /// Arguments the owning process was started with, when they can be read
+    pub command_line: Vec<String>,
     pub pid: u32,
     pub x: i32,
     pub y: i32,
@@ -13,6 +18,50 @@ pub struct SystemWindow {
     pub is_visible: bool,
     pub is_minimized: bool,
     pub is_maximized: bool,
+    pub is_fullscreen: bool,
+    /// Kept above other windows
+    pub is_above: bool,
+    /// Shown on every virtual desktop of the native window manager
+    pub is_sticky: bool,
+}
+
+/// Window states that can be toggled through the native window manager
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WindowStateFlag {
+    Maximized,
+    Fullscreen,
+    Above,
+    Sticky,
+}
+
+/// Name of the Tauri event native window changes are forwarded under
+pub const SYSTEM_WINDOW_EVENT: &str = "system-window-event";
+
+/// A change to a native window reported by a backend event stream
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum SystemWindowEvent {
+    Created { handle: u64 },
+    Destroyed { handle: u64 },
+    Mapped { handle: u64 },
+    Unmapped { handle: u64 },
+    /// Moved or resized; the position is relative to the screen
+    Configured { handle: u64, x: i32, y: i32, width: u32, height: u32 },
+    TitleChanged { handle: u64, title: String },
+    /// `None` when no window has focus
+    FocusChanged { handle: Option<u64> },
+    /// A panel or dock changed the space it reserves, or the window manager
+    /// changed the work area
+    WorkAreaChanged,
+}
+
+/// Target position and size of one window in a batched update
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct WindowBounds {
+    pub handle: u64,
+    pub x: i32,
+    pub y: i32,
+    pub width: u32,
+    pub height: u32,
 }
 
 pub trait SystemWindowManager {
@@ -28,6 +77,25 @@ pub trait SystemWindowManager {
     fn focus_window(handle: u64) -> Result<(), String>;
     fn hide_window(handle: u64) -> Result<(), String>;
     fn show_window(handle: u64) -> Result<(), String>;
+
+    /// Moves and resizes several windows at once. Backends that can batch
+    /// requests send them together; the default applies them one by one.
+    fn set_windows_bounds(bounds: &[WindowBounds]) -> Result<(), String> {
+        for b in bounds {
+            Self::set_window_position_and_size(b.handle, b.x, b.y, b.width, b.height)?;
+        }
+        Ok(())
+    }
+
+    /// Connected monitors. Platforms without enumeration return none, and
+    /// callers fall back to the configured screen size.
+    fn get_monitors() -> Result<Vec<Monitor>, String> {
+        Ok(Vec::new())
+    }
+
+    fn set_window_state(_handle: u64, _flag: WindowStateFlag, _enabled: bool) -> Result<(), String> {
+        Err("Window states are not supported on this platform".to_string())
+    }
 }
 
 #[cfg(windows)]
@@ -246,6 +314,8 @@ mod windows_impl {
             handle: hwnd as u64,
             title,
             process_name,
+            class: String::new(),
+            command_line: Vec::new(),
             pid,
             x: rect.left,
             y: rect.top,
@@ -254,6 +324,9 @@ mod windows_impl {
             is_visible: IsWindowVisible(hwnd) != 0,
             is_minimized: placement.showCmd == SW_SHOWMINIMIZED as u32,
             is_maximized: placement.showCmd == SW_SHOWMAXIMIZED as u32,
+            is_fullscreen: false,
+            is_above: false,
+            is_sticky: false,
         })
     }
 
@@ -553,6 +626,8 @@ mod macos_impl {
             handle: window_id,
             title,
             process_name,
+            class: String::new(),
+            command_line: Vec::new(),
             pid,
             x,
             y,
@@ -561,6 +636,9 @@ mod macos_impl {
             is_visible: true, // Assume visible since we're getting on-screen windows
             is_minimized: false, // Would need additional checks
             is_maximized: false, // Would need additional checks
+            is_fullscreen: false,
+            is_above: false,
+            is_sticky: false,
         })
     }
 }
@@ -569,169 +647,494 @@ mod macos_impl {
 mod linux_impl {
     use super::*;
     use x11::xlib::*;
+    use x11::xrandr::*;
+    use crate::layout::Rect;
+    use crate::monitor::ScreenStrut;
     use std::ptr;
     use std::ffi::{CString, CStr};
     use std::mem;
+    use std::cell::RefCell;
+    use std::collections::HashSet;
+    use std::os::raw::{c_char, c_int, c_long, c_uint, c_ulong, c_void};
+    use std::os::fd::{AsRawFd, FromRawFd, OwnedFd, RawFd};
+    use std::sync::mpsc::{self, Receiver, Sender, TryRecvError};
+    use std::sync::atomic::{AtomicPtr, Ordering};
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+    use once_cell::sync::Lazy;
 
-    pub struct LinuxManager;
+    type Job = Box<dyn FnOnce(*mut Display) + Send>;
 
-    impl SystemWindowManager for LinuxManager {
-        fn get_all_windows() -> Result<Vec<SystemWindow>, String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
-                let root = XDefaultRootWindow(display);
-                let mut windows = Vec::new();
-                
-                // Get all windows
-                let mut root_return = 0;
-                let mut parent_return = 0;
-                let mut children_return = ptr::null_mut();
-                let mut nchildren_return = 0;
-                
-                if XQueryTree(display, root, &mut root_return, &mut parent_return, 
-                             &mut children_return, &mut nchildren_return) != 0 {
-                    
-                    let children = std::slice::from_raw_parts(children_return, nchildren_return as usize);
-                    
-                    for &window in children {
-                        if let Ok(sys_window) = get_window_info(display, window) {
-                            if !sys_window.title.is_empty() && sys_window.is_visible {
-                                windows.push(sys_window);
-                            }
-                        }
+    /// A long-lived X server connection owned by a dedicated thread. Xlib is
+    /// not thread-safe, so every request runs on that thread as a job, and the
+    /// connection is synced once after each job so X errors it caused are
+    /// reported as its result. While idle the thread sleeps until either the
+    /// server sends something or a job arrives, which is signalled through
+    /// `wakeup`.
+    pub struct X11Backend {
+        jobs: Mutex<Sender<Job>>,
+        wakeup: Arc<OwnedFd>,
+        subscribers: Arc<Mutex<Vec<Sender<SystemWindowEvent>>>>,
+    }
+
+    /// Started on first use. A failure to start is kept, so every later call
+    /// reports it instead of panicking.
+    static BACKEND: Lazy<Result<X11Backend, String>> = Lazy::new(X11Backend::spawn);
+
+    impl X11Backend {
+        pub fn global() -> Result<&'static X11Backend, String> {
+            BACKEND.as_ref().map_err(Clone::clone)
+        }
+
+        fn spawn() -> Result<Self, String> {
+            let (sender, receiver) = mpsc::channel::<Job>();
+            let fd = unsafe { libc::eventfd(0, libc::EFD_CLOEXEC | libc::EFD_NONBLOCK) };
+            if fd < 0 {
+                return Err(format!("Failed to create X11 backend wakeup: {}", std::io::Error::last_os_error()));
+            }
+            let wakeup = Arc::new(unsafe { OwnedFd::from_raw_fd(fd) });
+
+            let subscribers = Arc::new(Mutex::new(Vec::new()));
+            let (thread_wakeup, thread_subscribers) = (wakeup.clone(), subscribers.clone());
+            thread::Builder::new()
+                .name("x11-backend".to_string())
+                .spawn(move || run_event_loop(receiver, thread_wakeup, thread_subscribers))
+                .map_err(|e| format!("Failed to spawn X11 backend thread: {}", e))?;
+
+            Ok(Self { jobs: Mutex::new(sender), wakeup, subscribers })
+        }
+
+        /// Starts watching windows and returns a channel of their changes.
+        /// Dropping the receiver unsubscribes.
+        pub fn subscribe(&self) -> Result<Receiver<SystemWindowEvent>, String> {
+            self.run(|display| unsafe {
+                watch_windows(display);
+                // Clients that vanished while being selected on are not an error
+                XSync(display, False);
+                take_x_error();
+                Ok(())
+            })?;
+
+            let (sender, receiver) = mpsc::channel();
+            self.subscribers.lock().unwrap().push(sender);
+            Ok(receiver)
+        }
+
+        /// Runs `f` against the connection on the backend thread and waits for
+        /// its result. Requests queued by `f` are sent with a single flush.
+        pub fn run<T, F>(&self, f: F) -> Result<T, String>
+        where
+            T: Send + 'static,
+            F: FnOnce(*mut Display) -> Result<T, String> + Send + 'static,
+        {
+            let (reply, result) = mpsc::channel();
+            let job: Job = Box::new(move |display| {
+                let outcome = if display.is_null() {
+                    Err("Cannot open X11 display".to_string())
+                } else {
+                    unsafe { install_error_handler() };
+                    take_x_error();
+                    let outcome = f(display);
+                    // Wait for the server to process the job's requests so
+                    // any error they raise has reached the handler
+                    unsafe { XSync(display, False) };
+                    match (outcome, take_x_error()) {
+                        (Ok(_), Some(error)) => Err(error),
+                        (outcome, _) => outcome,
                     }
-                    
-                    XFree(children_return as *mut _);
+                };
+                let _ = reply.send(outcome);
+            });
+
+            self.jobs
+                .lock()
+                .unwrap()
+                .send(job)
+                .map_err(|_| "X11 backend thread has stopped".to_string())?;
+            // Fails only when the counter is saturated, which wakes the thread anyway
+            let one: u64 = 1;
+            unsafe { libc::write(self.wakeup.as_raw_fd(), &one as *const u64 as *const c_void, mem::size_of::<u64>()) };
+            result.recv().map_err(|_| "X11 backend thread has stopped".to_string())?
+        }
+    }
+
+    fn run_event_loop(jobs: Receiver<Job>, wakeup: Arc<OwnedFd>, subscribers: Arc<Mutex<Vec<Sender<SystemWindowEvent>>>>) {
+        // The default handler exits the process on errors such as BadWindow
+        unsafe { install_error_handler() };
+        let mut display = unsafe { XOpenDisplay(ptr::null()) };
+        BACKEND_DISPLAY.store(display, Ordering::SeqCst);
+
+        'events: loop {
+            loop {
+                match jobs.try_recv() {
+                    Ok(job) => unsafe {
+                        // Retry in case the server was not up when the thread started
+                        if display.is_null() {
+                            display = XOpenDisplay(ptr::null());
+                            BACKEND_DISPLAY.store(display, Ordering::SeqCst);
+                        }
+                        job(display);
+                    },
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Disconnected) => break 'events,
                 }
-                
-                XCloseDisplay(display);
-                Ok(windows)
             }
+
+            // Jobs may have read events into Xlib's queue, so it is emptied
+            // before waiting on the socket
+            if !display.is_null() {
+                unsafe { drain_events(display, &subscribers) };
+            }
+            unsafe { wait_for_work(wakeup.as_raw_fd(), display) };
         }
 
-        fn get_window_by_handle(handle: u64) -> Result<Option<SystemWindow>, String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Ok(None);
+        if !display.is_null() {
+            unsafe { XCloseDisplay(display) };
+        }
+    }
+
+    /// Blocks until a job was sent or, when connected, the server has data
+    unsafe fn wait_for_work(wakeup: RawFd, display: *mut Display) {
+        let mut fds = [
+            libc::pollfd { fd: wakeup, events: libc::POLLIN, revents: 0 },
+            libc::pollfd { fd: -1, events: libc::POLLIN, revents: 0 },
+        ];
+        if !display.is_null() {
+            fds[1].fd = XConnectionNumber(display);
+        }
+        // Negative fds are ignored; EINTR just means going round again
+        libc::poll(fds.as_mut_ptr(), fds.len() as libc::nfds_t, -1);
+
+        if fds[0].revents & libc::POLLIN != 0 {
+            let mut count: u64 = 0;
+            libc::read(wakeup, &mut count as *mut u64 as *mut c_void, mem::size_of::<u64>());
+        }
+    }
+
+    thread_local! {
+        /// First X error raised on the backend connection since it was last
+        /// taken. Only the backend thread uses that connection, so the
+        /// handler always runs there for it.
+        static X_ERROR: RefCell<Option<String>> = const { RefCell::new(None) };
+    }
+
+    /// The backend's connection; errors on any other one are not ours
+    static BACKEND_DISPLAY: AtomicPtr<Display> = AtomicPtr::new(ptr::null_mut());
+
+    type XErrorHandler = Option<unsafe extern "C" fn(*mut Display, *mut XErrorEvent) -> c_int>;
+
+    /// Handler that was installed before ours, e.g. GDK's in a Tauri app
+    static PREVIOUS_ERROR_HANDLER: Mutex<XErrorHandler> = Mutex::new(None);
+
+    /// Makes `handle_x_error` the process-wide error handler, chaining to the
+    /// one it replaces. GDK may install its own at any time, so this runs
+    /// again before every job.
+    unsafe fn install_error_handler() {
+        let ours: unsafe extern "C" fn(*mut Display, *mut XErrorEvent) -> c_int = handle_x_error;
+        let current = XSetErrorHandler(Some(ours));
+        if current.map(|handler| handler as usize) != Some(ours as usize) {
+            *PREVIOUS_ERROR_HANDLER.lock().unwrap_or_else(|e| e.into_inner()) = current;
+        }
+    }
+
+    unsafe extern "C" fn handle_x_error(display: *mut Display, event: *mut XErrorEvent) -> c_int {
+        if display != BACKEND_DISPLAY.load(Ordering::SeqCst) {
+            let previous = *PREVIOUS_ERROR_HANDLER.lock().unwrap_or_else(|e| e.into_inner());
+            return match previous {
+                Some(handler) => handler(display, event),
+                None => 0,
+            };
+        }
+
+        let event = &*event;
+        let mut text = [0 as c_char; 256];
+        XGetErrorText(display, event.error_code as c_int, text.as_mut_ptr(), text.len() as c_int);
+        let message = format!(
+            "{} (request {}.{} on 0x{:x})",
+            CStr::from_ptr(text.as_ptr()).to_string_lossy(),
+            event.request_code,
+            event.minor_code,
+            event.resourceid,
+        );
+
+        X_ERROR.with(|error| {
+            error.borrow_mut().get_or_insert(message);
+        });
+        0
+    }
+
+    /// Returns and clears the pending X error, e.g. `BadWindow` for a window
+    /// that was destroyed
+    fn take_x_error() -> Option<String> {
+        X_ERROR.with(|error| error.borrow_mut().take())
+    }
+
+    thread_local! {
+        /// Windows selected for structure and property events; `None` until
+        /// the first subscription
+        static WATCHED: RefCell<Option<HashSet<Window>>> = const { RefCell::new(None) };
+    }
+
+    unsafe fn watch_windows(display: *mut Display) {
+        let root = XDefaultRootWindow(display);
+        let first = WATCHED.with(|watched| {
+            let mut watched = watched.borrow_mut();
+            let first = watched.is_none();
+            watched.get_or_insert_with(HashSet::new);
+            first
+        });
+        if first {
+            // New top-levels, the client list and the active window are announced on the root
+            XSelectInput(display, root, SubstructureNotifyMask | PropertyChangeMask);
+        }
+        watch_new_clients(display, root);
+    }
+
+    /// Selects on clients not watched yet and returns them
+    unsafe fn watch_new_clients(display: *mut Display, root: Window) -> Vec<Window> {
+        get_client_windows(display, root)
+            .into_iter()
+            .filter(|&client| watch_window(display, client))
+            .collect()
+    }
+
+    /// Selects on a client's own structure and property events. Returns
+    /// false when it was already watched.
+    unsafe fn watch_window(display: *mut Display, window: Window) -> bool {
+        let added = WATCHED.with(|watched| {
+            watched.borrow_mut().as_mut().is_some_and(|set| set.insert(window))
+        });
+        if added {
+            XSelectInput(display, window, StructureNotifyMask | PropertyChangeMask);
+        }
+        added
+    }
+
+    fn is_watched(window: Window) -> bool {
+        WATCHED.with(|watched| watched.borrow().as_ref().is_some_and(|set| set.contains(&window)))
+    }
+
+    fn unwatch(window: Window) {
+        WATCHED.with(|watched| {
+            if let Some(set) = watched.borrow_mut().as_mut() {
+                set.remove(&window);
+            }
+        });
+    }
+
+    /// Reads queued events and hands them to subscribers, dropping those
+    /// whose receiver is gone
+    unsafe fn drain_events(display: *mut Display, subscribers: &Mutex<Vec<Sender<SystemWindowEvent>>>) {
+        let mut event: XEvent = mem::zeroed();
+        while XPending(display) > 0 {
+            XNextEvent(display, &mut event);
+            for window_event in translate_event(display, &event) {
+                subscribers.lock().unwrap().retain(|s| s.send(window_event.clone()).is_ok());
+            }
+        }
+    }
+
+    // Xlib event type constants are CamelCase, and `Window` is only a u64 on
+    // 64-bit targets
+    #[allow(non_upper_case_globals, clippy::unnecessary_cast)]
+    unsafe fn translate_event(display: *mut Display, event: &XEvent) -> Vec<SystemWindowEvent> {
+        let root = XDefaultRootWindow(display);
+
+        // Structure events of a client arrive both through its own mask and,
+        // for direct children, the root's substructure mask. Only the former
+        // are reported, so frames of a reparenting window manager are skipped.
+        match event.get_type() {
+            CreateNotify => {
+                let e = event.create_window;
+                // With EWMH, new clients are picked up from _NET_CLIENT_LIST
+                // instead, since this would also report frames
+                if e.parent == root && e.override_redirect == 0 && !has_ewmh_window_manager(display, root)
+                    && watch_window(display, e.window)
+                {
+                    return vec![SystemWindowEvent::Created { handle: e.window as u64 }];
                 }
-                
-                let window = handle as Window;
-                let result = get_window_info(display, window);
-                XCloseDisplay(display);
-                
-                match result {
-                    Ok(window_info) => Ok(Some(window_info)),
-                    Err(_) => Ok(None),
+                Vec::new()
+            }
+            DestroyNotify => {
+                let e = event.destroy_window;
+                if e.event == e.window && is_watched(e.window) {
+                    unwatch(e.window);
+                    return vec![SystemWindowEvent::Destroyed { handle: e.window as u64 }];
+                }
+                Vec::new()
+            }
+            MapNotify => {
+                let e = event.map;
+                if e.event == e.window && is_watched(e.window) {
+                    return vec![SystemWindowEvent::Mapped { handle: e.window as u64 }];
+                }
+                Vec::new()
+            }
+            UnmapNotify => {
+                let e = event.unmap;
+                if e.event == e.window && is_watched(e.window) {
+                    return vec![SystemWindowEvent::Unmapped { handle: e.window as u64 }];
+                }
+                Vec::new()
+            }
+            ConfigureNotify => {
+                let e = event.configure;
+                if e.event != e.window || !is_watched(e.window) {
+                    return Vec::new();
                 }
+                // Positions are relative to the parent, which may be a frame
+                let mut x = e.x;
+                let mut y = e.y;
+                let mut child = 0;
+                XTranslateCoordinates(display, e.window, root, 0, 0, &mut x, &mut y, &mut child);
+                vec![SystemWindowEvent::Configured {
+                    handle: e.window as u64,
+                    x,
+                    y,
+                    width: e.width as u32,
+                    height: e.height as u32,
+                }]
             }
+            PropertyNotify => {
+                let e = event.property;
+                if e.window == root {
+                    if e.atom == intern_atom(display, b"_NET_ACTIVE_WINDOW\0", false) {
+                        let active = get_property_values(display, root, b"_NET_ACTIVE_WINDOW\0", XA_WINDOW)
+                            .and_then(|values| values.first().copied())
+                            .filter(|&window| window != 0);
+                        return vec![SystemWindowEvent::FocusChanged { handle: active.map(|w| w as u64) }];
+                    }
+                    if e.atom == intern_atom(display, b"_NET_CLIENT_LIST\0", false) {
+                        return watch_new_clients(display, root)
+                            .into_iter()
+                            .map(|window| SystemWindowEvent::Created { handle: window as u64 })
+                            .collect();
+                    }
+                    if e.atom == intern_atom(display, b"_NET_WORKAREA\0", false) {
+                        return vec![SystemWindowEvent::WorkAreaChanged];
+                    }
+                } else if is_watched(e.window)
+                    && (e.atom == intern_atom(display, b"_NET_WM_STRUT\0", false)
+                        || e.atom == intern_atom(display, b"_NET_WM_STRUT_PARTIAL\0", false))
+                {
+                    return vec![SystemWindowEvent::WorkAreaChanged];
+                } else if is_watched(e.window)
+                    && (e.atom == XA_WM_NAME || e.atom == intern_atom(display, b"_NET_WM_NAME\0", false))
+                {
+                    return vec![SystemWindowEvent::TitleChanged {
+                        handle: e.window as u64,
+                        title: get_window_title(display, e.window),
+                    }];
+                }
+                Vec::new()
+            }
+            _ => Vec::new(),
+        }
+    }
+
+    pub struct LinuxManager;
+
+    impl SystemWindowManager for LinuxManager {
+        fn get_all_windows() -> Result<Vec<SystemWindow>, String> {
+            X11Backend::global()?.run(|display| unsafe {
+                let root = XDefaultRootWindow(display);
+                let clients = get_client_windows(display, root);
+
+                let windows = clients.into_iter()
+                    .filter_map(|window| get_window_info(display, window).ok())
+                    .filter(|window| !window.title.is_empty() && window.is_visible)
+                    .collect();
+
+                // Clients that closed while being listed are simply skipped
+                XSync(display, False);
+                take_x_error();
+                Ok(windows)
+            })
+        }
+
+        fn get_window_by_handle(handle: u64) -> Result<Option<SystemWindow>, String> {
+            // A backend failure is an error; only a stale handle is no window
+            X11Backend::global()?.run(move |display| unsafe {
+                let window = get_window_info(display, handle as Window).ok();
+                XSync(display, False);
+                take_x_error();
+                Ok(window)
+            })
         }
 
         fn move_window(handle: u64, x: i32, y: i32) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
-                let window = handle as Window;
-                XMoveWindow(display, window, x, y);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+            X11Backend::global()?.run(move |display| unsafe {
+                XMoveWindow(display, handle as Window, x, y);
+                Ok(())
+            })
         }
 
         fn resize_window(handle: u64, width: u32, height: u32) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
-                let window = handle as Window;
-                XResizeWindow(display, window, width, height);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+            X11Backend::global()?.run(move |display| unsafe {
+                XResizeWindow(display, handle as Window, width, height);
+                Ok(())
+            })
         }
 
         fn set_window_position_and_size(handle: u64, x: i32, y: i32, width: u32, height: u32) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
+            X11Backend::global()?.run(move |display| unsafe {
+                XMoveResizeWindow(display, handle as Window, x, y, width, height);
+                Ok(())
+            })
+        }
+
+        fn set_windows_bounds(bounds: &[WindowBounds]) -> Result<(), String> {
+            let bounds = bounds.to_vec();
+            X11Backend::global()?.run(move |display| unsafe {
+                for b in bounds {
+                    XMoveResizeWindow(display, b.handle as Window, b.x, b.y, b.width, b.height);
                 }
-                
-                let window = handle as Window;
-                XMoveResizeWindow(display, window, x, y, width, height);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+                Ok(())
+            })
         }
 
         fn minimize_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
-                let window = handle as Window;
-                XIconifyWindow(display, window, XDefaultScreen(display));
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+            X11Backend::global()?.run(move |display| unsafe {
+                XIconifyWindow(display, handle as Window, XDefaultScreen(display));
+                Ok(())
+            })
         }
 
         fn maximize_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
+            X11Backend::global()?.run(move |display| unsafe {
                 let window = handle as Window;
-                let screen = XDefaultScreen(display);
-                let screen_width = XDisplayWidth(display, screen) as u32;
-                let screen_height = XDisplayHeight(display, screen) as u32;
-                
-                XMoveResizeWindow(display, window, 0, 0, screen_width, screen_height);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+                let root = XDefaultRootWindow(display);
+                if has_ewmh_window_manager(display, root) {
+                    let atoms = NetWmStateAtoms::new(display);
+                    send_net_wm_state(display, window, NET_WM_STATE_ADD, atoms.maximized_vert, atoms.maximized_horz);
+                } else {
+                    // Without a window manager the best we can do is cover the display
+                    let screen = XDefaultScreen(display);
+                    let screen_width = XDisplayWidth(display, screen) as u32;
+                    let screen_height = XDisplayHeight(display, screen) as u32;
+                    XMoveResizeWindow(display, window, 0, 0, screen_width, screen_height);
+                }
+                Ok(())
+            })
         }
 
         fn restore_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
+            X11Backend::global()?.run(move |display| unsafe {
                 let window = handle as Window;
+                let root = XDefaultRootWindow(display);
+                if has_ewmh_window_manager(display, root) {
+                    let atoms = NetWmStateAtoms::new(display);
+                    send_net_wm_state(display, window, NET_WM_STATE_REMOVE, atoms.maximized_vert, atoms.maximized_horz);
+                    send_net_wm_state(display, window, NET_WM_STATE_REMOVE, atoms.fullscreen, 0);
+                }
+                // Mapping an iconic window de-iconifies it
                 XMapWindow(display, window);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+                Ok(())
+            })
         }
 
         fn close_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
+            X11Backend::global()?.run(move |display| unsafe {
                 let window = handle as Window;
                 
                 // Try to close gracefully first
@@ -746,56 +1149,50 @@ mod linux_impl {
                 event.client_message.data.set_long(0, wm_delete_window as i64);
                 
                 XSendEvent(display, window, 0, NoEventMask, &mut event);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+                Ok(())
+            })
         }
 
         fn focus_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
+            X11Backend::global()?.run(move |display| unsafe {
                 let window = handle as Window;
                 XRaiseWindow(display, window);
                 XSetInputFocus(display, window, RevertToParent, CurrentTime);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+                Ok(())
+            })
         }
 
         fn hide_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
-                }
-                
-                let window = handle as Window;
-                XUnmapWindow(display, window);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+            X11Backend::global()?.run(move |display| unsafe {
+                XUnmapWindow(display, handle as Window);
+                Ok(())
+            })
         }
 
         fn show_window(handle: u64) -> Result<(), String> {
-            unsafe {
-                let display = XOpenDisplay(ptr::null());
-                if display.is_null() {
-                    return Err("Cannot open X11 display".to_string());
+            X11Backend::global()?.run(move |display| unsafe {
+                XMapWindow(display, handle as Window);
+                Ok(())
+            })
+        }
+
+        fn get_monitors() -> Result<Vec<Monitor>, String> {
+            X11Backend::global()?.run(|display| unsafe { Ok(query_monitors(display)) })
+        }
+
+        fn set_window_state(handle: u64, flag: WindowStateFlag, enabled: bool) -> Result<(), String> {
+            X11Backend::global()?.run(move |display| unsafe {
+                let root = XDefaultRootWindow(display);
+                if !has_ewmh_window_manager(display, root) {
+                    return Err("No EWMH window manager is running".to_string());
                 }
-                
-                let window = handle as Window;
-                XMapWindow(display, window);
-                XFlush(display);
-                XCloseDisplay(display);
-            }
-            Ok(())
+
+                let atoms = NetWmStateAtoms::new(display);
+                let (first, second) = atoms.flag_atoms(flag);
+                let action = if enabled { NET_WM_STATE_ADD } else { NET_WM_STATE_REMOVE };
+                send_net_wm_state(display, handle as Window, action, first, second);
+                Ok(())
+            })
         }
     }
     
@@ -805,26 +1202,17 @@ mod linux_impl {
             return Err("Failed to get window attributes".to_string());
         }
         
-        // Get window title
-        let mut window_name = ptr::null_mut();
-        let title = if XFetchName(display, window, &mut window_name) != 0 && !window_name.is_null() {
-            let c_str = CStr::from_ptr(window_name);
-            let title = c_str.to_string_lossy().to_string();
-            XFree(window_name as *mut _);
-            title
-        } else {
-            String::new()
-        };
+        let title = get_window_title(display, window);
         
-        // Get window class (process name)
+        // Get window class
         let mut class_hint: XClassHint = mem::zeroed();
-        let process_name = if XGetClassHint(display, window, &mut class_hint) != 0 {
+        let class = if XGetClassHint(display, window, &mut class_hint) != 0 {
             let name = if !class_hint.res_class.is_null() {
                 CStr::from_ptr(class_hint.res_class).to_string_lossy().to_string()
             } else if !class_hint.res_name.is_null() {
                 CStr::from_ptr(class_hint.res_name).to_string_lossy().to_string()
             } else {
-                "Unknown".to_string()
+                String::new()
             };
             
             if !class_hint.res_name.is_null() {
@@ -836,8 +1224,23 @@ mod linux_impl {
             
             name
         } else {
-            "Unknown".to_string()
+            String::new()
         };
+
+        // Resolve the owning process, falling back to the class when it is
+        // unknown. `_NET_WM_PID` of a client on another host names no local
+        // process, so only XRes, which knows local clients only, is asked then
+        let pid = match get_window_pid(display, window) {
+            pid if pid != 0 && is_local_client(display, window) => pid,
+            _ => get_window_pid_xres(display, window),
+        };
+        let process_name = process_exe_name(pid)
+            .or_else(|| if class.is_empty() { None } else { Some(class.clone()) })
+            .unwrap_or_else(|| "Unknown".to_string());
+        let command_line = process_command_line(pid);
+
+        let atoms = NetWmStateAtoms::new(display);
+        let state = get_property_values(display, window, b"_NET_WM_STATE\0", XA_ATOM).unwrap_or_default();
         
         // Get window position relative to root
         let mut x_return = 0;
@@ -849,16 +1252,650 @@ mod linux_impl {
             handle: window as u64,
             title,
             process_name,
-            pid: 0, // Would need additional system calls to get PID
+            class,
+            command_line,
+            pid,
             x: x_return,
             y: y_return,
             width: attrs.width as u32,
             height: attrs.height as u32,
             is_visible: attrs.map_state == IsViewable,
-            is_minimized: attrs.map_state == IsUnmapped,
-            is_maximized: false, // Would need additional checks
+            is_minimized: state.contains(&atoms.hidden) || is_iconic(display, window),
+            is_maximized: state.contains(&atoms.maximized_vert) && state.contains(&atoms.maximized_horz),
+            is_fullscreen: state.contains(&atoms.fullscreen),
+            is_above: state.contains(&atoms.above),
+            is_sticky: state.contains(&atoms.sticky),
+        })
+    }
+
+    /// RandR monitors, or active outputs on servers older than RandR 1.5.
+    /// Empty when the server lacks the extension.
+    unsafe fn query_monitors(display: *mut Display) -> Vec<Monitor> {
+        let mut event_base = 0;
+        let mut error_base = 0;
+        if XRRQueryExtension(display, &mut event_base, &mut error_base) == 0 {
+            return Vec::new();
+        }
+
+        let root = XDefaultRootWindow(display);
+        let mut monitors = match query_randr_monitors(display, root) {
+            Some(monitors) => monitors,
+            None => query_outputs(display, root),
+        };
+
+        // Without a primary output configured, treat the first one as primary
+        if !monitors.iter().any(|m| m.primary) {
+            if let Some(first) = monitors.first_mut() {
+                first.primary = true;
+            }
+        }
+        reserve_work_areas(display, root, &mut monitors);
+        monitors
+    }
+
+    /// RandR 1.5 monitors, which include areas split off an output with
+    /// `xrandr --setmonitor`. None when the server does not support them.
+    unsafe fn query_randr_monitors(display: *mut Display, root: Window) -> Option<Vec<Monitor>> {
+        let mut major = 0;
+        let mut minor = 0;
+        if XRRQueryVersion(display, &mut major, &mut minor) == 0 || (major, minor) < (1, 5) {
+            return None;
+        }
+
+        let mut count = 0;
+        let infos = XRRGetMonitors(display, root, True, &mut count);
+        if infos.is_null() {
+            return None;
+        }
+        let monitors: Vec<Monitor> = std::slice::from_raw_parts(infos, count.max(0) as usize)
+            .iter()
+            .map(|info| {
+                let name = get_atom_name(display, info.name);
+                let geometry = Rect {
+                    x: info.x,
+                    y: info.y,
+                    width: info.width as u32,
+                    height: info.height as u32,
+                };
+                Monitor {
+                    id: name.clone(),
+                    name,
+                    geometry,
+                    work_area: geometry,
+                    primary: info.primary != 0,
+                    scale: Monitor::scale_for_size(info.width as u32, info.mwidth as u64),
+                }
+            })
+            .collect();
+        XRRFreeMonitors(infos);
+
+        (!monitors.is_empty()).then_some(monitors)
+    }
+
+    /// Active RandR outputs, i.e. connected ones driven by a CRTC
+    unsafe fn query_outputs(display: *mut Display, root: Window) -> Vec<Monitor> {
+        let resources = XRRGetScreenResourcesCurrent(display, root);
+        if resources.is_null() {
+            return Vec::new();
+        }
+        let primary = XRRGetOutputPrimary(display, root);
+
+        let outputs = std::slice::from_raw_parts((*resources).outputs, (*resources).noutput as usize);
+        let mut monitors = Vec::new();
+        for &output in outputs {
+            let info = XRRGetOutputInfo(display, resources, output);
+            if info.is_null() {
+                continue;
+            }
+
+            if (*info).connection as c_int == RR_Connected && (*info).crtc != 0 {
+                let crtc = XRRGetCrtcInfo(display, resources, (*info).crtc);
+                if !crtc.is_null() {
+                    let name = String::from_utf8_lossy(std::slice::from_raw_parts(
+                        (*info).name as *const u8,
+                        (*info).nameLen as usize,
+                    ))
+                    .to_string();
+
+                    let geometry = Rect {
+                        x: (*crtc).x,
+                        y: (*crtc).y,
+                        width: (*crtc).width,
+                        height: (*crtc).height,
+                    };
+                    monitors.push(Monitor {
+                        id: name.clone(),
+                        name,
+                        geometry,
+                        work_area: geometry,
+                        primary: output == primary,
+                        scale: Monitor::scale_for_size((*crtc).width, (*info).mm_width as u64),
+                    });
+                    XRRFreeCrtcInfo(crtc);
+                }
+            }
+            XRRFreeOutputInfo(info);
+        }
+        XRRFreeScreenResources(resources);
+
+        monitors
+    }
+
+    unsafe fn get_atom_name(display: *mut Display, atom: Atom) -> String {
+        let name = XGetAtomName(display, atom);
+        if name.is_null() {
+            return String::new();
+        }
+        let result = CStr::from_ptr(name).to_string_lossy().to_string();
+        XFree(name as *mut _);
+        result
+    }
+
+    /// Shrinks each monitor's work area by the struts of panels and docks.
+    /// `_NET_WORKAREA` is one rectangle spanning all monitors, so it is only
+    /// trusted with a single monitor.
+    unsafe fn reserve_work_areas(display: *mut Display, root: Window, monitors: &mut [Monitor]) {
+        let screen = XDefaultScreen(display);
+        let screen_width = XDisplayWidth(display, screen) as u32;
+        let screen_height = XDisplayHeight(display, screen) as u32;
+
+        // Docks are not always in the client list, so also look at the
+        // top-level windows
+        let mut windows = get_client_windows(display, root);
+        for child in query_children(display, root) {
+            if !windows.contains(&child) {
+                windows.push(child);
+            }
+        }
+        // Unmapped windows, such as hidden panels, reserve nothing
+        for window in windows.into_iter().filter(|&window| is_viewable(display, window)) {
+            if let Some(strut) = get_window_strut(display, window) {
+                for monitor in monitors.iter_mut() {
+                    monitor.reserve_screen_strut(&strut, screen_width, screen_height);
+                }
+            }
+        }
+
+        if let [monitor] = monitors {
+            if let Some(work_area) = get_net_work_area(display, root) {
+                monitor.work_area = intersect(monitor.work_area, work_area);
+            }
+        }
+    }
+
+    unsafe fn is_viewable(display: *mut Display, window: Window) -> bool {
+        let mut attributes: XWindowAttributes = mem::zeroed();
+        XGetWindowAttributes(display, window, &mut attributes) != 0 && attributes.map_state == IsViewable
+    }
+
+    /// `_NET_WM_STRUT_PARTIAL`, or the older `_NET_WM_STRUT` which spans
+    /// whole edges
+    unsafe fn get_window_strut(display: *mut Display, window: Window) -> Option<ScreenStrut> {
+        if let Some(v) = get_property_values(display, window, b"_NET_WM_STRUT_PARTIAL\0", XA_CARDINAL).filter(|v| v.len() >= 12) {
+            return Some(ScreenStrut {
+                left: v[0] as u32,
+                right: v[1] as u32,
+                top: v[2] as u32,
+                bottom: v[3] as u32,
+                left_start_y: v[4] as u32,
+                left_end_y: v[5] as u32,
+                right_start_y: v[6] as u32,
+                right_end_y: v[7] as u32,
+                top_start_x: v[8] as u32,
+                top_end_x: v[9] as u32,
+                bottom_start_x: v[10] as u32,
+                bottom_end_x: v[11] as u32,
+            });
+        }
+
+        let v = get_property_values(display, window, b"_NET_WM_STRUT\0", XA_CARDINAL).filter(|v| v.len() >= 4)?;
+        Some(ScreenStrut {
+            left: v[0] as u32,
+            right: v[1] as u32,
+            top: v[2] as u32,
+            bottom: v[3] as u32,
+            left_end_y: u32::MAX - 1,
+            right_end_y: u32::MAX - 1,
+            top_end_x: u32::MAX - 1,
+            bottom_end_x: u32::MAX - 1,
+            ..Default::default()
+        })
+    }
+
+    /// The `_NET_WORKAREA` entry of the current desktop
+    unsafe fn get_net_work_area(display: *mut Display, root: Window) -> Option<Rect> {
+        let areas = get_property_values(display, root, b"_NET_WORKAREA\0", XA_CARDINAL)?;
+        let desktop = get_property_values(display, root, b"_NET_CURRENT_DESKTOP\0", XA_CARDINAL)
+            .and_then(|v| v.first().copied())
+            .unwrap_or(0) as usize;
+        let area = areas.chunks_exact(4).nth(desktop).or_else(|| areas.chunks_exact(4).next())?;
+        Some(Rect {
+            x: area[0] as i32,
+            y: area[1] as i32,
+            width: area[2] as u32,
+            height: area[3] as u32,
         })
     }
+
+    fn intersect(a: Rect, b: Rect) -> Rect {
+        let left = a.x.max(b.x);
+        let top = a.y.max(b.y);
+        let right = (a.x as i64 + a.width as i64).min(b.x as i64 + b.width as i64);
+        let bottom = (a.y as i64 + a.height as i64).min(b.y as i64 + b.height as i64);
+        Rect {
+            x: left,
+            y: top,
+            width: (right - left as i64).max(0) as u32,
+            height: (bottom - top as i64).max(0) as u32,
+        }
+    }
+
+    /// `_NET_WM_NAME`, which is UTF-8, or else the legacy `WM_NAME`
+    unsafe fn get_window_title(display: *mut Display, window: Window) -> String {
+        if let Some(title) = get_utf8_property(display, window, b"_NET_WM_NAME\0") {
+            return title;
+        }
+
+        let mut window_name = ptr::null_mut();
+        if XFetchName(display, window, &mut window_name) != 0 && !window_name.is_null() {
+            let title = CStr::from_ptr(window_name).to_string_lossy().to_string();
+            XFree(window_name as *mut _);
+            title
+        } else {
+            String::new()
+        }
+    }
+
+    unsafe fn intern_atom(display: *mut Display, name: &[u8], only_if_exists: bool) -> Atom {
+        XInternAtom(display, name.as_ptr() as *const i8, if only_if_exists { True } else { False })
+    }
+
+    /// Reads a `UTF8_STRING` property, or `None` when the window does not have it
+    unsafe fn get_utf8_property(display: *mut Display, window: Window, name: &[u8]) -> Option<String> {
+        let property = intern_atom(display, name, true);
+        let utf8_string = intern_atom(display, b"UTF8_STRING\0", true);
+        if property == 0 || utf8_string == 0 {
+            return None;
+        }
+
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut nitems = 0;
+        let mut bytes_after = 0;
+        let mut prop: *mut u8 = ptr::null_mut();
+
+        let status = XGetWindowProperty(
+            display, window, property, 0, c_long::MAX / 4, False, utf8_string,
+            &mut actual_type, &mut actual_format, &mut nitems, &mut bytes_after, &mut prop,
+        );
+
+        let value = if status == Success as i32 && !prop.is_null() && actual_type == utf8_string && actual_format == 8 {
+            let bytes = std::slice::from_raw_parts(prop, nitems as usize);
+            Some(String::from_utf8_lossy(bytes).to_string())
+        } else {
+            None
+        };
+
+        if !prop.is_null() {
+            XFree(prop as *mut _);
+        }
+        value
+    }
+
+    /// Reads a format 32 property as a list of values, or `None` when the
+    /// window does not have it with the requested type
+    unsafe fn get_property_values(display: *mut Display, window: Window, name: &[u8], property_type: Atom) -> Option<Vec<c_ulong>> {
+        let property = intern_atom(display, name, true);
+        if property == 0 {
+            return None;
+        }
+
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut nitems = 0;
+        let mut bytes_after = 0;
+        let mut prop: *mut u8 = ptr::null_mut();
+
+        // The length is in 32-bit units; ask for far more than any list holds
+        let status = XGetWindowProperty(
+            display, window, property, 0, c_long::MAX / 4, False, property_type,
+            &mut actual_type, &mut actual_format, &mut nitems, &mut bytes_after, &mut prop,
+        );
+
+        let values = if status == Success as i32 && !prop.is_null() && actual_type == property_type && actual_format == 32 {
+            // Format 32 properties are returned as an array of C longs
+            Some(std::slice::from_raw_parts(prop as *const c_ulong, nitems as usize).to_vec())
+        } else {
+            None
+        };
+
+        if !prop.is_null() {
+            XFree(prop as *mut _);
+        }
+        values
+    }
+
+    /// Reads the `_NET_WM_PID` property set by the client, or 0 when missing
+    unsafe fn get_window_pid(display: *mut Display, window: Window) -> u32 {
+        get_property_values(display, window, b"_NET_WM_PID\0", XA_CARDINAL)
+            .and_then(|values| values.first().copied())
+            .map(|pid| pid as u32)
+            .unwrap_or(0)
+    }
+
+    /// Whether `WM_CLIENT_MACHINE` names this host. Clients are expected to
+    /// set it along with `_NET_WM_PID`; without it the host is unknown.
+    unsafe fn is_local_client(display: *mut Display, window: Window) -> bool {
+        let mut machine: XTextProperty = mem::zeroed();
+        if XGetWMClientMachine(display, window, &mut machine) == 0 || machine.value.is_null() {
+            return false;
+        }
+        let client = String::from_utf8_lossy(std::slice::from_raw_parts(machine.value, machine.nitems as usize)).to_string();
+        XFree(machine.value as *mut _);
+
+        local_hostname().is_some_and(|host| host == client)
+    }
+
+    fn local_hostname() -> Option<String> {
+        let mut name = [0u8; 256];
+        if unsafe { libc::gethostname(name.as_mut_ptr() as *mut c_char, name.len()) } != 0 {
+            return None;
+        }
+        let len = name.iter().position(|&b| b == 0).unwrap_or(name.len());
+        Some(String::from_utf8_lossy(&name[..len]).to_string())
+    }
+
+    const NET_WM_STATE_REMOVE: c_long = 0;
+    const NET_WM_STATE_ADD: c_long = 1;
+    const ICONIC_STATE: c_ulong = 3;
+
+    struct NetWmStateAtoms {
+        maximized_vert: c_ulong,
+        maximized_horz: c_ulong,
+        hidden: c_ulong,
+        fullscreen: c_ulong,
+        above: c_ulong,
+        sticky: c_ulong,
+    }
+
+    impl NetWmStateAtoms {
+        unsafe fn new(display: *mut Display) -> Self {
+            Self {
+                maximized_vert: intern_atom(display, b"_NET_WM_STATE_MAXIMIZED_VERT\0", false),
+                maximized_horz: intern_atom(display, b"_NET_WM_STATE_MAXIMIZED_HORZ\0", false),
+                hidden: intern_atom(display, b"_NET_WM_STATE_HIDDEN\0", false),
+                fullscreen: intern_atom(display, b"_NET_WM_STATE_FULLSCREEN\0", false),
+                above: intern_atom(display, b"_NET_WM_STATE_ABOVE\0", false),
+                sticky: intern_atom(display, b"_NET_WM_STATE_STICKY\0", false),
+            }
+        }
+
+        /// The one or two atoms a flag is made of
+        fn flag_atoms(&self, flag: WindowStateFlag) -> (c_ulong, c_ulong) {
+            match flag {
+                WindowStateFlag::Maximized => (self.maximized_vert, self.maximized_horz),
+                WindowStateFlag::Fullscreen => (self.fullscreen, 0),
+                WindowStateFlag::Above => (self.above, 0),
+                WindowStateFlag::Sticky => (self.sticky, 0),
+            }
+        }
+    }
+
+    /// ICCCM iconic state, for window managers without `_NET_WM_STATE_HIDDEN`
+    unsafe fn is_iconic(display: *mut Display, window: Window) -> bool {
+        let wm_state = intern_atom(display, b"WM_STATE\0", true);
+        if wm_state == 0 {
+            return false;
+        }
+        get_property_values(display, window, b"WM_STATE\0", wm_state)
+            .and_then(|values| values.first().copied())
+            == Some(ICONIC_STATE)
+    }
+
+    /// Asks the window manager to add or remove `_NET_WM_STATE` atoms, which
+    /// clients may not change on their own once a window is mapped
+    unsafe fn send_net_wm_state(display: *mut Display, window: Window, action: c_long, first: c_ulong, second: c_ulong) {
+        let root = XDefaultRootWindow(display);
+        let mut event: XEvent = mem::zeroed();
+        event.client_message.type_ = ClientMessage;
+        event.client_message.window = window;
+        event.client_message.message_type = intern_atom(display, b"_NET_WM_STATE\0", false);
+        event.client_message.format = 32;
+        event.client_message.data.set_long(0, action);
+        event.client_message.data.set_long(1, first as c_long);
+        event.client_message.data.set_long(2, second as c_long);
+        // Source indication: a normal application
+        event.client_message.data.set_long(3, 1);
+
+        XSendEvent(display, root, False, SubstructureRedirectMask | SubstructureNotifyMask, &mut event);
+    }
+
+    /// Whether an EWMH compliant window manager is running, which it signals
+    /// by a `_NET_SUPPORTING_WM_CHECK` child window that points at itself
+    unsafe fn has_ewmh_window_manager(display: *mut Display, root: Window) -> bool {
+        let check = b"_NET_SUPPORTING_WM_CHECK\0";
+        match get_property_values(display, root, check, XA_WINDOW).and_then(|v| v.first().copied()) {
+            Some(child) => get_property_values(display, child, check, XA_WINDOW)
+                .and_then(|v| v.first().copied())
+                == Some(child),
+            None => false,
+        }
+    }
+
+    /// Top-level client windows, bottom to top where the stacking order is known
+    unsafe fn get_client_windows(display: *mut Display, root: Window) -> Vec<Window> {
+        if has_ewmh_window_manager(display, root) {
+            let clients = get_property_values(display, root, b"_NET_CLIENT_LIST_STACKING\0", XA_WINDOW)
+                .or_else(|| get_property_values(display, root, b"_NET_CLIENT_LIST\0", XA_WINDOW));
+            if let Some(clients) = clients {
+                return clients;
+            }
+        }
+
+        // Without EWMH, clients are the windows carrying WM_STATE, which a
+        // reparenting window manager nests inside its frames. Like
+        // XmuClientWindow, a top-level window without one in its subtree is
+        // its own client, which covers running without any window manager.
+        let wm_state = intern_atom(display, b"WM_STATE\0", true);
+        let mut clients = Vec::new();
+        for child in query_children(display, root) {
+            let found = clients.len();
+            find_clients(display, child, wm_state, &mut clients);
+            if clients.len() == found {
+                clients.push(child);
+            }
+        }
+        clients
+    }
+
+    unsafe fn find_clients(display: *mut Display, window: Window, wm_state: Atom, clients: &mut Vec<Window>) {
+        if wm_state != 0 && has_property(display, window, wm_state) {
+            clients.push(window);
+            return;
+        }
+        for child in query_children(display, window) {
+            find_clients(display, child, wm_state, clients);
+        }
+    }
+
+    unsafe fn has_property(display: *mut Display, window: Window, property: Atom) -> bool {
+        let mut actual_type = 0;
+        let mut actual_format = 0;
+        let mut nitems = 0;
+        let mut bytes_after = 0;
+        let mut prop: *mut u8 = ptr::null_mut();
+
+        let status = XGetWindowProperty(
+            display, window, property, 0, 0, False, AnyPropertyType as Atom,
+            &mut actual_type, &mut actual_format, &mut nitems, &mut bytes_after, &mut prop,
+        );
+        if !prop.is_null() {
+            XFree(prop as *mut _);
+        }
+        status == Success as i32 && actual_type != 0
+    }
+
+    /// Children of `window` in stacking order, bottom first
+    unsafe fn query_children(display: *mut Display, window: Window) -> Vec<Window> {
+        let mut root_return = 0;
+        let mut parent_return = 0;
+        let mut children_return = ptr::null_mut();
+        let mut nchildren_return = 0;
+
+        if XQueryTree(display, window, &mut root_return, &mut parent_return,
+                      &mut children_return, &mut nchildren_return) == 0 {
+            return Vec::new();
+        }
+
+        if children_return.is_null() {
+            return Vec::new();
+        }
+        let children = std::slice::from_raw_parts(children_return, nchildren_return as usize).to_vec();
+        XFree(children_return as *mut _);
+        children
+    }
+
+    const XRES_CLIENT_ID_PID_MASK: c_uint = 1 << 1;
+
+    #[repr(C)]
+    struct XResClientIdSpec {
+        client: XID,
+        mask: c_uint,
+    }
+
+    #[repr(C)]
+    struct XResClientIdValue {
+        spec: XResClientIdSpec,
+        length: c_long,
+        value: *mut c_void,
+    }
+
+    type XResQueryExtension = unsafe extern "C" fn(*mut Display, *mut c_int, *mut c_int) -> Bool;
+    type XResQueryClientIds = unsafe extern "C" fn(
+        *mut Display, c_long, *mut XResClientIdSpec, *mut c_long, *mut *mut XResClientIdValue,
+    ) -> Status;
+    type XResGetClientPid = unsafe extern "C" fn(*mut XResClientIdValue) -> libc::pid_t;
+    type XResClientIdsDestroy = unsafe extern "C" fn(c_long, *mut XResClientIdValue);
+
+    /// Entry points of libXRes, which is loaded at runtime since not every
+    /// system ships it
+    struct XRes {
+        query_extension: XResQueryExtension,
+        query_client_ids: XResQueryClientIds,
+        get_client_pid: XResGetClientPid,
+        client_ids_destroy: XResClientIdsDestroy,
+    }
+
+    unsafe impl Send for XRes {}
+    unsafe impl Sync for XRes {}
+
+    static XRES: Lazy<Option<XRes>> = Lazy::new(|| unsafe {
+        let lib = libc::dlopen(c"libXRes.so.1".as_ptr(), libc::RTLD_LAZY);
+        if lib.is_null() {
+            return None;
+        }
+
+        let symbol = |name: &[u8]| libc::dlsym(lib, name.as_ptr() as *const _);
+        let symbols = [
+            symbol(b"XResQueryExtension\0"),
+            symbol(b"XResQueryClientIds\0"),
+            symbol(b"XResGetClientPid\0"),
+            symbol(b"XResClientIdsDestroy\0"),
+        ];
+        if symbols.iter().any(|s| s.is_null()) {
+            return None;
+        }
+
+        Some(XRes {
+            query_extension: mem::transmute::<*mut c_void, XResQueryExtension>(symbols[0]),
+            query_client_ids: mem::transmute::<*mut c_void, XResQueryClientIds>(symbols[1]),
+            get_client_pid: mem::transmute::<*mut c_void, XResGetClientPid>(symbols[2]),
+            client_ids_destroy: mem::transmute::<*mut c_void, XResClientIdsDestroy>(symbols[3]),
+        })
+    });
+
+    /// Asks the server which local client owns `window` through the XRes
+    /// extension, for clients that do not set `_NET_WM_PID`. Returns 0 when
+    /// libXRes or the extension is unavailable.
+    unsafe fn get_window_pid_xres(display: *mut Display, window: Window) -> u32 {
+        let xres = match XRES.as_ref() {
+            Some(xres) => xres,
+            None => return 0,
+        };
+
+        let mut event_base = 0;
+        let mut error_base = 0;
+        if (xres.query_extension)(display, &mut event_base, &mut error_base) == 0 {
+            return 0;
+        }
+
+        let mut spec = XResClientIdSpec { client: window, mask: XRES_CLIENT_ID_PID_MASK };
+        let mut num_ids: c_long = 0;
+        let mut ids: *mut XResClientIdValue = ptr::null_mut();
+        if (xres.query_client_ids)(display, 1, &mut spec, &mut num_ids, &mut ids) != Success as Status {
+            return 0;
+        }
+
+        let mut pid = 0;
+        for i in 0..num_ids as usize {
+            let value = ids.add(i);
+            if (*value).spec.mask & XRES_CLIENT_ID_PID_MASK != 0 {
+                let client_pid = (xres.get_client_pid)(value);
+                if client_pid > 0 {
+                    pid = client_pid as u32;
+                    break;
+                }
+            }
+        }
+
+        (xres.client_ids_destroy)(num_ids, ids);
+        pid
+    }
+
+    /// Executable name from `/proc/<pid>/exe`, or `comm` when the link is unreadable
+    fn process_exe_name(pid: u32) -> Option<String> {
+        if pid == 0 {
+            return None;
+        }
+        std::fs::read_link(format!("/proc/{}/exe", pid))
+            .ok()
+            .and_then(|path| path.file_name().map(|name| name.to_string_lossy().to_string()))
+            .or_else(|| {
+                std::fs::read_to_string(format!("/proc/{}/comm", pid))
+                    .ok()
+                    .map(|comm| comm.trim_end().to_string())
+            })
+    }
+
+    /// Arguments from `/proc/<pid>/cmdline`, which are NUL separated
+    fn process_command_line(pid: u32) -> Vec<String> {
+        if pid == 0 {
+            return Vec::new();
+        }
+        std::fs::read(format!("/proc/{}/cmdline", pid))
+            .map(|bytes| {
+                bytes.split(|&b| b == 0)
+                    .filter(|arg| !arg.is_empty())
+                    .map(|arg| String::from_utf8_lossy(arg).to_string())
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+}
+
+/// Parent process id read from `/proc/<pid>/stat`
+#[cfg(target_os = "linux")]
+pub fn parent_pid(pid: u32) -> Option<u32> {
+    let stat = std::fs::read_to_string(format!("/proc/{}/stat", pid)).ok()?;
+    // The command name is wrapped in parentheses and may itself contain spaces
+    // or parentheses, so parse from the last ')'
+    let fields: Vec<&str> = stat[stat.rfind(')')? + 1..].split_whitespace().collect();
+    fields.get(1)?.parse().ok()
+}
+
+#[cfg(not(target_os = "linux"))]
+pub fn parent_pid(_pid: u32) -> Option<u32> {
+    None
 }
 
 // Platform-specific type alias
@@ -869,4 +1906,7 @@ pub type PlatformWindowManager = windows_impl::WindowsManager;
 pub type PlatformWindowManager = macos_impl::MacOSManager;
 
 #[cfg(target_os = "linux")]
-pub type PlatformWindowManager = linux_impl::LinuxManager;
\ No newline at end of file
+pub type PlatformWindowManager = linux_impl::LinuxManager;
+
+#[cfg(target_os = "linux")]
+pub use linux_impl::X11Backend;
\ No newline at end of file
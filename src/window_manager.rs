@@ -1,14 +1,24 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::{Arc, Mutex};
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
 use chrono::{DateTime, Utc};
-use crate::workspace::Workspace;
-use crate::layout::LayoutType;
+use crate::workspace::{OrphanPolicy, Workspace};
+use crate::scratchpad::{Scratchpad, SCRATCHPAD_SIZE_RATIO, SCRATCHPAD_WORKSPACE_ID};
+use crate::layout::{
+    neighbor_in_direction, tab_headers, tiling_grid, BspLayout, CenteredMasterLayout, Direction, FloatingLayout, Layout, LayoutType,
+    MasterStackLayout, MonocleLayout, Rect, StackedLayout, TabInfo, TabbedLayout, ThreeColumnLayout,
+    TilingLayout, MIN_TILE_SIZE,
+};
+use crate::bsp::{BspNode, SplitDirection};
 use crate::config::Config;
-use crate::system_window::{SystemWindow, SystemWindowManager, PlatformWindowManager};
+use crate::monitor::{Monitor, Strut};
+use crate::events::{self, WindowFocused, WindowRemoved, WindowsArranged, WorkspaceDeleted, WorkspaceSwitched};
+use crate::hooks::{AfterHook, BeforeHook, HookResult, Hooks, WindowAdd};
+use crate::rules::{evaluate_rules, RuleActions};
+use crate::system_window::{parent_pid, SystemWindow, SystemWindowManager, PlatformWindowManager, WindowBounds, WindowStateFlag};
 
-use tauri::command;
+use tauri::{command, AppHandle, Emitter, Runtime};
 use once_cell::sync::Lazy;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -22,6 +32,17 @@ pub struct ManagedWindow {
     pub state: WindowState,
     pub created_at: DateTime<Utc>,
     pub last_focused: DateTime<Utc>,
+    /// Sticky windows follow the active workspace instead of belonging to one
+    pub sticky: bool,
+    /// Floating windows keep their own position and size and are left out of tiling
+    pub floating: bool,
+    /// Native handle when the window was adopted from a system window
+    pub handle: Option<u64>,
+    pub pid: Option<u32>,
+    /// Terminals are swallowed by GUI windows launched from them
+    pub terminal: bool,
+    /// Id of the terminal hidden while this window holds its slot
+    pub swallowed: Option<String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -44,23 +65,62 @@ pub enum WindowState {
     Fullscreen,
 }
 
+impl ManagedWindow {
+    pub fn rect(&self) -> Rect {
+        Rect {
+            x: self.position.x,
+            y: self.position.y,
+            width: self.size.width,
+            height: self.size.height,
+        }
+    }
+}
+
+/// Receives every state change event with its serialized payload. It is called
+/// without any lock held, so it may call back into the `WindowManager`.
+pub type EventSink = Arc<dyn Fn(&str, serde_json::Value) + Send + Sync>;
+
 pub struct WindowManager {
     pub windows: Arc<Mutex<HashMap<String, ManagedWindow>>>,
     pub workspaces: Arc<Mutex<HashMap<String, Workspace>>>,
     pub active_workspace: Arc<Mutex<String>>,
     pub config: Arc<Mutex<Config>>,
     pub system_windows: Arc<Mutex<HashMap<u64, SystemWindow>>>,
+    pub layouts: Arc<Mutex<HashMap<String, Arc<dyn Layout>>>>,
+    pub scratchpad: Arc<Mutex<Scratchpad>>,
+    /// Rule actions of every system window seen by `get_system_windows`
+    pub system_window_rules: Arc<Mutex<HashMap<u64, RuleActions>>>,
+    /// Where state change events go; nothing is emitted until one is set
+    pub event_sink: Arc<Mutex<Option<EventSink>>>,
+    /// Registered through the `on_*` methods
+    pub(crate) hooks: Arc<Mutex<Hooks>>,
+    /// Monitors from the last query; `None` until first needed
+    pub monitors: Arc<Mutex<Option<Vec<Monitor>>>>,
+    /// Space the app keeps free on each monitor, by monitor id
+    pub reserved_struts: Arc<Mutex<HashMap<String, Strut>>>,
+    /// Where monitors are queried; the platform, unless a test stands in
+    pub(crate) monitor_source: fn() -> Result<Vec<Monitor>, String>,
 }
 
 impl WindowManager {
     pub fn new() -> Self {
+        // Workspaces are always keyed by their own id
         let mut workspaces = HashMap::new();
-        let default_workspace_id = Uuid::new_v4().to_string();
+        let default_workspace = Workspace::new("Default".to_string(), LayoutType::Tiling);
+        let default_workspace_id = default_workspace.id.clone();
         
-        workspaces.insert(
-            default_workspace_id.clone(),
-            Workspace::new("Default".to_string(), LayoutType::Tiling)
-        );
+        workspaces.insert(default_workspace_id.clone(), default_workspace);
+
+        let mut layouts: HashMap<String, Arc<dyn Layout>> = HashMap::new();
+        layouts.insert(LayoutType::Tiling.name().to_string(), Arc::new(TilingLayout));
+        layouts.insert(LayoutType::Floating.name().to_string(), Arc::new(FloatingLayout));
+        layouts.insert(LayoutType::Monocle.name().to_string(), Arc::new(MonocleLayout));
+        layouts.insert(LayoutType::MasterStack.name().to_string(), Arc::new(MasterStackLayout));
+        layouts.insert(LayoutType::Bsp.name().to_string(), Arc::new(BspLayout));
+        layouts.insert(LayoutType::CenteredMaster.name().to_string(), Arc::new(CenteredMasterLayout));
+        layouts.insert(LayoutType::ThreeColumn.name().to_string(), Arc::new(ThreeColumnLayout));
+        layouts.insert(LayoutType::Tabbed.name().to_string(), Arc::new(TabbedLayout));
+        layouts.insert(LayoutType::Stacked.name().to_string(), Arc::new(StackedLayout));
 
         Self {
             windows: Arc::new(Mutex::new(HashMap::new())),
@@ -68,46 +128,558 @@ impl WindowManager {
             active_workspace: Arc::new(Mutex::new(default_workspace_id)),
             config: Arc::new(Mutex::new(Config::default())),
             system_windows: Arc::new(Mutex::new(HashMap::new())),
+            layouts: Arc::new(Mutex::new(layouts)),
+            scratchpad: Arc::new(Mutex::new(Scratchpad::new())),
+            system_window_rules: Arc::new(Mutex::new(HashMap::new())),
+            event_sink: Arc::new(Mutex::new(None)),
+            hooks: Arc::new(Mutex::new(Hooks::default())),
+            monitors: Arc::new(Mutex::new(None)),
+            reserved_struts: Arc::new(Mutex::new(HashMap::new())),
+            monitor_source: PlatformWindowManager::get_monitors,
+        }
+    }
+
+    /// Runs the "before" hooks picked by `select` on a copy of the list, so
+    /// hooks can register others or call back into the manager
+    fn run_before_hooks<T>(&self, select: fn(&Hooks) -> &[BeforeHook<T>], value: &mut T) -> Result<(), String> {
+        let hooks = select(&self.hooks.lock().unwrap()).to_vec();
+        Hooks::run_before(&hooks, value)
+    }
+
+    fn run_after_hooks<T>(&self, select: fn(&Hooks) -> &[AfterHook<T>], value: &T) {
+        let hooks = select(&self.hooks.lock().unwrap()).to_vec();
+        Hooks::run_after(&hooks, value);
+    }
+
+    /// Runs before a window is added; may rename or redirect it
+    pub fn on_before_window_add(&self, hook: impl Fn(&mut WindowAdd) -> HookResult + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_before_window_add(hook);
+    }
+
+    /// Runs after a window was added
+    pub fn on_after_window_add(&self, hook: impl Fn(&ManagedWindow) + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_after_window_add(hook);
+    }
+
+    /// Runs before a window is removed, with its id
+    pub fn on_before_window_remove(&self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_before_window_remove(hook);
+    }
+
+    /// Runs after a window was removed
+    pub fn on_after_window_remove(&self, hook: impl Fn(&WindowRemoved) + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_after_window_remove(hook);
+    }
+
+    /// Runs before a window is focused; changing the id focuses another window
+    pub fn on_before_focus(&self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_before_focus(hook);
+    }
+
+    /// Runs after a window was focused
+    pub fn on_after_focus(&self, hook: impl Fn(&WindowFocused) + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_after_focus(hook);
+    }
+
+    /// Runs before switching to the workspace with the given id
+    pub fn on_before_workspace_switch(&self, hook: impl Fn(&mut String) -> HookResult + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_before_workspace_switch(hook);
+    }
+
+    /// Runs after the active workspace changed
+    pub fn on_after_workspace_switch(&self, hook: impl Fn(&WorkspaceSwitched) + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_after_workspace_switch(hook);
+    }
+
+    /// Runs on the snapshot a workspace is about to be arranged from; a veto
+    /// leaves the windows where they are
+    pub fn on_before_arrange(&self, hook: impl Fn(&mut Workspace) -> HookResult + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_before_arrange(hook);
+    }
+
+    /// Runs after a workspace was arranged
+    pub fn on_after_arrange(&self, hook: impl Fn(&WindowsArranged) + Send + Sync + 'static) {
+        self.hooks.lock().unwrap().on_after_arrange(hook);
+    }
+
+    /// Emits every state change as a Tauri event through `app_handle`. The
+    /// plugin calls this in its setup.
+    pub fn set_app_handle<R: Runtime>(&self, app_handle: AppHandle<R>) {
+        *self.event_sink.lock().unwrap() = Some(Arc::new(move |event, payload| {
+            if let Err(e) = app_handle.emit(event, payload) {
+                log::warn!("Failed to emit {}: {}", event, e);
+            }
+        }));
+    }
+
+    fn emit<S: Serialize>(&self, event: &str, payload: &S) {
+        // Tauri runs Rust listeners synchronously, and they may re-enter
+        let sink = self.event_sink.lock().unwrap().clone();
+        if let Some(sink) = sink {
+            match serde_json::to_value(payload) {
+                Ok(payload) => sink(event, payload),
+                Err(e) => log::warn!("Failed to serialize {} payload: {}", event, e),
+            }
+        }
+    }
+
+    fn emit_window(&self, event: &str, window_id: &str) {
+        let window = self.windows.lock().unwrap().get(window_id).cloned();
+        if let Some(window) = window {
+            self.emit(event, &window);
+        }
+    }
+
+    fn emit_workspace(&self, event: &str, workspace_id: &str) {
+        if let Some(workspace) = self.get_workspace(workspace_id) {
+            self.emit(event, &workspace);
         }
     }
 
     pub fn add_window(&self, title: String, app_name: String) -> Result<String, String> {
+        self.insert_window(title, app_name, None, None, None)
+    }
+
+    /// Adopts a system window as a managed window. When its process descends
+    /// from a managed terminal, it swallows that terminal.
+    pub fn manage_system_window(&self, handle: u64) -> Result<String, String> {
+        if let Some(window) = self.windows.lock().unwrap().values().find(|w| w.handle == Some(handle)) {
+            return Ok(window.id.clone());
+        }
+
+        let system_window = match self.system_windows.lock().unwrap().get(&handle).cloned() {
+            Some(window) => window,
+            None => PlatformWindowManager::get_window_by_handle(handle)?.ok_or("System window not found")?,
+        };
+        let pid = if system_window.pid == 0 { None } else { Some(system_window.pid) };
+        let process_name = system_window.process_name.clone();
+
+        self.insert_window(system_window.title, process_name.clone(), Some(&process_name), Some(handle), pid)
+    }
+
+    fn insert_window(
+        &self,
+        title: String,
+        app_name: String,
+        process_name: Option<&str>,
+        handle: Option<u64>,
+        pid: Option<u32>,
+    ) -> Result<String, String> {
         let window_id = Uuid::new_v4().to_string();
-        let active_workspace = self.active_workspace.lock().unwrap().clone();
+        let actions = evaluate_rules(&self.config.lock().unwrap().rules, Some(&app_name), process_name, &title);
+        let target_workspace = actions.workspace
+            .as_deref()
+            .and_then(|workspace| self.resolve_workspace(workspace))
+            .unwrap_or_else(|| self.active_workspace.lock().unwrap().clone());
+
+        let mut request = WindowAdd { title, app_name, workspace_id: target_workspace };
+        self.run_before_hooks(|h| &h.before_window_add, &mut request)?;
+        let WindowAdd { title, app_name, workspace_id } = request;
+        let target_workspace = self.resolve_workspace(&workspace_id).ok_or("Workspace not found")?;
         
         let window = ManagedWindow {
             id: window_id.clone(),
             title,
             app_name,
-            workspace_id: active_workspace.clone(),
+            workspace_id: target_workspace.clone(),
             position: WindowPosition { x: 0, y: 0 },
-            size: WindowSize { width: 800, height: 600 },
-            state: WindowState::Normal,
+            size: WindowSize {
+                width: actions.width.unwrap_or(800),
+                height: actions.height.unwrap_or(600),
+            },
+            state: if actions.start_minimized.unwrap_or(false) {
+                WindowState::Minimized
+            } else {
+                WindowState::Normal
+            },
             created_at: Utc::now(),
             last_focused: Utc::now(),
+            sticky: actions.sticky.unwrap_or(false),
+            floating: actions.floating.unwrap_or(false) || actions.ignore.unwrap_or(false),
+            handle,
+            pid,
+            terminal: actions.terminal.unwrap_or(false),
+            swallowed: None,
         };
 
+        let swallow_target = match pid {
+            Some(pid) if !window.terminal && !window.floating => self.find_parent_terminal(pid),
+            _ => None,
+        };
         self.windows.lock().unwrap().insert(window_id.clone(), window);
+
+        if let Some(terminal_id) = swallow_target {
+            self.swallow_terminal(&window_id, &terminal_id)?;
+            self.window_added(&window_id);
+            return Ok(window_id);
+        }
         
-        if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&active_workspace) {
+        if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&target_workspace) {
             workspace.add_window(window_id.clone());
         }
 
-        self.arrange_workspace(&active_workspace)?;
-        
+        self.arrange_workspace(&target_workspace)?;
+
+        if actions.floating.unwrap_or(false) {
+            self.center_floating_window(&window_id)?;
+        }
+
+        self.window_added(&window_id);
         Ok(window_id)
     }
 
+    fn window_added(&self, window_id: &str) {
+        let window = self.windows.lock().unwrap().get(window_id).cloned();
+        if let Some(window) = window {
+            self.emit(events::WINDOW_ADDED, &window);
+            self.run_after_hooks(|h| &h.after_window_add, &window);
+        }
+    }
+
+    /// Finds a visible managed terminal whose process is an ancestor of `pid`
+    fn find_parent_terminal(&self, pid: u32) -> Option<String> {
+        let terminals: Vec<(String, u32)> = self.windows.lock().unwrap()
+            .values()
+            .filter(|w| w.terminal && !matches!(w.state, WindowState::Minimized) && w.workspace_id != SCRATCHPAD_WORKSPACE_ID)
+            .filter_map(|w| w.pid.map(|terminal_pid| (w.id.clone(), terminal_pid)))
+            .collect();
+
+        if terminals.is_empty() {
+            return None;
+        }
+
+        // Walks up from `pid` so the nearest terminal wins when they nest;
+        // reading /proc happens without holding the windows lock
+        let mut current = pid;
+        while let Some(parent) = parent_pid(current) {
+            if let Some((id, _)) = terminals.iter().find(|(_, terminal_pid)| *terminal_pid == parent) {
+                return Some(id.clone());
+            }
+            if parent <= 1 {
+                return None;
+            }
+            current = parent;
+        }
+        None
+    }
+
+    /// Hides `terminal_id` and gives its slot in the workspace order to `window_id`
+    fn swallow_terminal(&self, window_id: &str, terminal_id: &str) -> Result<(), String> {
+        let (workspace_id, terminal_handle) = {
+            let mut windows = self.windows.lock().unwrap();
+            let terminal = windows.get_mut(terminal_id).ok_or("Window not found")?;
+            terminal.state = WindowState::Minimized;
+            let swallowed = (terminal.workspace_id.clone(), terminal.handle);
+
+            if let Some(window) = windows.get_mut(window_id) {
+                window.workspace_id = swallowed.0.clone();
+                window.swallowed = Some(terminal_id.to_string());
+            }
+            swallowed
+        };
+
+        if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&workspace_id) {
+            workspace.replace_window(terminal_id, window_id.to_string());
+        }
+
+        if let Some(handle) = terminal_handle {
+            self.hide_system_window(handle)?;
+        }
+
+        self.arrange_workspace(&workspace_id)?;
+        self.emit_window(events::WINDOW_UPDATED, terminal_id);
+        Ok(())
+    }
+
+    /// Puts a swallowed terminal back into the slot held by `window_id`, which
+    /// must still be in its workspace. Returns whether the terminal was restored.
+    fn restore_swallowed_terminal(&self, window_id: &str, terminal_id: &str, workspace_id: &str) -> Result<bool, String> {
+        let on_scratchpad = workspace_id == SCRATCHPAD_WORKSPACE_ID;
+        // On a hidden scratchpad the terminal stays hidden until summoned
+        let visible = !on_scratchpad || self.scratchpad.lock().unwrap().visible;
+
+        let terminal_handle = {
+            let mut windows = self.windows.lock().unwrap();
+            match windows.get_mut(terminal_id) {
+                Some(terminal) => {
+                    terminal.state = if visible { WindowState::Normal } else { WindowState::Minimized };
+                    terminal.workspace_id = workspace_id.to_string();
+                    terminal.handle
+                }
+                None => return Ok(false),
+            }
+        };
+
+        if on_scratchpad {
+            self.scratchpad.lock().unwrap().workspace.replace_window(window_id, terminal_id.to_string());
+        } else if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(workspace_id) {
+            workspace.replace_window(window_id, terminal_id.to_string());
+        }
+
+        if let Some(handle) = terminal_handle.filter(|_| visible) {
+            self.show_system_window(handle)?;
+        }
+        self.emit_window(events::WINDOW_UPDATED, terminal_id);
+        Ok(true)
+    }
+
+    /// Resolves a workspace id or name to an id
+    fn resolve_workspace(&self, id_or_name: &str) -> Option<String> {
+        if self.workspaces.lock().unwrap().contains_key(id_or_name) {
+            return Some(id_or_name.to_string());
+        }
+        self.get_workspace_by_name(id_or_name).map(|w| w.id)
+    }
+
     pub fn remove_window(&self, window_id: &str) -> Result<(), String> {
-        if let Some(window) = self.windows.lock().unwrap().remove(window_id) {
-            if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&window.workspace_id) {
+        let mut window_id = window_id.to_string();
+        self.run_before_hooks(|h| &h.before_window_remove, &mut window_id)?;
+
+        let window = self.windows.lock().unwrap().remove(&window_id).ok_or("Window not found")?;
+        self.detach_window(&window_id, &window)?;
+
+        let removed = WindowRemoved { window_id, workspace_id: window.workspace_id };
+        self.emit(events::WINDOW_REMOVED, &removed);
+        self.run_after_hooks(|h| &h.after_window_remove, &removed);
+        Ok(())
+    }
+
+    /// Takes a window that was just removed from `windows` out of its workspace
+    fn detach_window(&self, window_id: &str, window: &ManagedWindow) -> Result<(), String> {
+        if window.sticky {
+            // It may hold the focus of workspaces it was visiting
+            for workspace in self.workspaces.lock().unwrap().values_mut() {
+                if workspace.focused_window.as_deref() == Some(window_id) && !workspace.windows.iter().any(|id| id == window_id) {
+                    workspace.focused_window = workspace.windows.last().cloned();
+                }
+            }
+        }
+
+        let on_scratchpad = window.workspace_id == SCRATCHPAD_WORKSPACE_ID;
+        let restored = match &window.swallowed {
+            Some(terminal_id) => self.restore_swallowed_terminal(window_id, terminal_id, &window.workspace_id)?,
+            None => false,
+        };
+
+        if on_scratchpad {
+            if !restored {
+                self.scratchpad.lock().unwrap().remove_window(window_id);
+            }
+            self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+            return Ok(());
+        }
+        if restored {
+            return self.arrange_workspace(&window.workspace_id);
+        }
+
+        let removed = match self.workspaces.lock().unwrap().get_mut(&window.workspace_id) {
+            Some(workspace) => {
                 workspace.remove_window(window_id);
-                self.arrange_workspace(&window.workspace_id)?;
+                true
             }
-            Ok(())
+            None => false,
+        };
+
+        // Both locks must be released before arranging, which takes them again
+        if removed {
+            self.arrange_workspace(&window.workspace_id)?;
+        }
+        Ok(())
+    }
+
+    /// Moves a window to another workspace, re-arranging both. With `follow`
+    /// the target workspace also becomes the active one.
+    pub fn move_window_to_workspace(&self, window_id: &str, workspace_id: &str, follow: bool) -> Result<(), String> {
+        if !self.workspaces.lock().unwrap().contains_key(workspace_id) {
+            return Err("Workspace not found".to_string());
+        }
+
+        let source_workspace = {
+            let mut windows = self.windows.lock().unwrap();
+            let window = windows.get_mut(window_id).ok_or("Window not found")?;
+            std::mem::replace(&mut window.workspace_id, workspace_id.to_string())
+        };
+
+        if source_workspace == SCRATCHPAD_WORKSPACE_ID {
+            self.scratchpad.lock().unwrap().remove_window(window_id);
+            let handle = self.windows.lock().unwrap().get_mut(window_id).and_then(|window| {
+                window.state = WindowState::Normal;
+                window.handle
+            });
+            // It may have been hidden with the scratchpad
+            if let Some(handle) = handle {
+                self.show_system_window(handle)?;
+            }
+            self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+        }
+
+        if source_workspace != workspace_id {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            if let Some(source) = workspaces.get_mut(&source_workspace) {
+                source.remove_window(window_id);
+            }
+            if let Some(target) = workspaces.get_mut(workspace_id) {
+                target.add_window(window_id.to_string());
+            }
+            drop(workspaces);
+
+            if self.workspaces.lock().unwrap().contains_key(&source_workspace) {
+                self.arrange_workspace(&source_workspace)?;
+            }
+            self.arrange_workspace(workspace_id)?;
+        }
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+
+        if follow {
+            self.switch_workspace(workspace_id)?;
+        }
+
+        Ok(())
+    }
+
+    /// Parks a managed window on the scratchpad, taking it out of its workspace
+    pub fn send_to_scratchpad(&self, window_id: &str) -> Result<(), String> {
+        let source_workspace = {
+            let mut windows = self.windows.lock().unwrap();
+            let window = windows.get_mut(window_id).ok_or("Window not found")?;
+            std::mem::replace(&mut window.workspace_id, SCRATCHPAD_WORKSPACE_ID.to_string())
+        };
+
+        if source_workspace == SCRATCHPAD_WORKSPACE_ID {
+            return Ok(());
+        }
+
+        let removed = match self.workspaces.lock().unwrap().get_mut(&source_workspace) {
+            Some(workspace) => {
+                workspace.remove_window(window_id);
+                true
+            }
+            None => false,
+        };
+        if removed {
+            self.arrange_workspace(&source_workspace)?;
+        }
+
+        self.scratchpad.lock().unwrap().workspace.add_window(window_id.to_string());
+        self.apply_scratchpad_visibility()?;
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+        self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+        Ok(())
+    }
+
+    /// Parks a system window on the scratchpad, hiding it until summoned
+    pub fn send_system_window_to_scratchpad(&self, handle: u64) -> Result<(), String> {
+        let visible = {
+            let mut scratchpad = self.scratchpad.lock().unwrap();
+            scratchpad.add_system_window(handle);
+            scratchpad.visible
+        };
+
+        if visible {
+            self.show_scratchpad_system_window(handle)?;
         } else {
-            Err("Window not found".to_string())
+            self.hide_system_window(handle)?;
+        }
+        self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+        Ok(())
+    }
+
+    /// Takes a system window off the scratchpad and makes sure it is shown
+    pub fn remove_system_window_from_scratchpad(&self, handle: u64) -> Result<(), String> {
+        if !self.scratchpad.lock().unwrap().remove_system_window(handle) {
+            return Err("Window is not on the scratchpad".to_string());
+        }
+
+        self.show_system_window(handle)?;
+        self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+        Ok(())
+    }
+
+    /// Shows or hides every scratchpad window as a centered overlay on top of
+    /// the active workspace. Returns whether the scratchpad is now visible.
+    pub fn toggle_scratchpad(&self) -> Result<bool, String> {
+        let visible = {
+            let mut scratchpad = self.scratchpad.lock().unwrap();
+            if scratchpad.is_empty() {
+                return Err("Scratchpad is empty".to_string());
+            }
+            scratchpad.visible = !scratchpad.visible;
+            scratchpad.visible
+        };
+
+        self.apply_scratchpad_visibility()?;
+        self.emit(events::SCRATCHPAD_CHANGED, &self.get_scratchpad());
+        Ok(visible)
+    }
+
+    pub fn get_scratchpad(&self) -> Scratchpad {
+        self.scratchpad.lock().unwrap().clone()
+    }
+
+    fn scratchpad_rect(&self) -> Rect {
+        // The scratchpad overlays the active workspace, on its monitor
+        let screen = self.get_workspace(&self.get_active_workspace())
+            .map(|workspace| self.workspace_area(&workspace))
+            .unwrap_or_else(|| self.screen_area());
+        let width = (screen.width as f32 * SCRATCHPAD_SIZE_RATIO) as u32;
+        let height = (screen.height as f32 * SCRATCHPAD_SIZE_RATIO) as u32;
+
+        Rect {
+            x: screen.x + ((screen.width - width) / 2) as i32,
+            y: screen.y + ((screen.height - height) / 2) as i32,
+            width,
+            height,
+        }
+    }
+
+    fn show_scratchpad_system_window(&self, handle: u64) -> Result<(), String> {
+        let rect = self.scratchpad_rect();
+        self.show_system_window(handle)?;
+        self.set_system_window_bounds(handle, rect.x, rect.y, rect.width, rect.height)?;
+        self.focus_system_window(handle)
+    }
+
+    fn apply_scratchpad_visibility(&self) -> Result<(), String> {
+        let (window_ids, mut handles, visible) = {
+            let scratchpad = self.scratchpad.lock().unwrap();
+            (
+                scratchpad.workspace.windows.clone(),
+                scratchpad.system_windows.clone(),
+                scratchpad.visible,
+            )
+        };
+        let rect = self.scratchpad_rect();
+
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for window_id in &window_ids {
+                if let Some(window) = windows.get_mut(window_id) {
+                    // Adopted windows are shown and placed natively as well
+                    handles.extend(window.handle);
+                    if visible {
+                        window.state = WindowState::Normal;
+                        window.position = WindowPosition { x: rect.x, y: rect.y };
+                        window.size = WindowSize {
+                            width: rect.width,
+                            height: rect.height,
+                        };
+                        window.last_focused = Utc::now();
+                    } else {
+                        window.state = WindowState::Minimized;
+                    }
+                }
+            }
         }
+
+        for &handle in &handles {
+            if visible {
+                self.show_scratchpad_system_window(handle)?;
+            } else {
+                self.hide_system_window(handle)?;
+            }
+        }
+
+        Ok(())
     }
 
     pub fn get_windows(&self) -> Vec<ManagedWindow> {
@@ -124,120 +696,961 @@ impl WindowManager {
             .collect()
     }
 
-    pub fn focus_window(&self, window_id: &str) -> Result<(), String> {
-        if let Some(window) = self.windows.lock().unwrap().get_mut(window_id) {
-            window.last_focused = Utc::now();
-            
-            if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&window.workspace_id) {
-                workspace.focus_window(window_id);
+    pub fn minimize_window(&self, window_id: &str) -> Result<(), String> {
+        self.set_window_state(window_id, WindowState::Minimized)
+    }
+
+    pub fn maximize_window(&self, window_id: &str) -> Result<(), String> {
+        self.set_window_state(window_id, WindowState::Maximized)
+    }
+
+    fn set_window_state(&self, window_id: &str, state: WindowState) -> Result<(), String> {
+        self.windows
+            .lock()
+            .unwrap()
+            .get_mut(window_id)
+            .ok_or("Window not found")?
+            .state = state;
+
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+        Ok(())
+    }
+
+    pub fn focus_window(&self, window_id: &str) -> Result<(), String> {
+        let mut window_id = window_id.to_string();
+        self.run_before_hooks(|h| &h.before_focus, &mut window_id)?;
+        let window_id = window_id.as_str();
+
+        let active_workspace = self.get_active_workspace();
+        let workspace_id = match self.windows.lock().unwrap().get_mut(window_id) {
+            Some(window) => {
+                window.last_focused = Utc::now();
+
+                // Sticky windows are focused where they are tiled, the active workspace
+                let workspace_id = if window.sticky && window.workspace_id != SCRATCHPAD_WORKSPACE_ID {
+                    active_workspace
+                } else {
+                    window.workspace_id.clone()
+                };
+                if let Some(workspace) = self.workspaces.lock().unwrap().get_mut(&workspace_id) {
+                    if workspace_id == window.workspace_id {
+                        workspace.focus_window(window_id);
+                    } else {
+                        workspace.focused_window = Some(window_id.to_string());
+                    }
+                }
+
+                workspace_id
+            }
+            None => return Err("Window not found".to_string()),
+        };
+
+        if self.layout_depends_on_focus(&workspace_id) {
+            self.arrange_workspace(&workspace_id)?;
+        }
+
+        let focused = WindowFocused { window_id: window_id.to_string(), workspace_id };
+        self.emit(events::WINDOW_FOCUSED, &focused);
+        self.run_after_hooks(|h| &h.after_focus, &focused);
+        Ok(())
+    }
+
+    fn layout_depends_on_focus(&self, workspace_id: &str) -> bool {
+        let layout = match self.workspaces.lock().unwrap().get(workspace_id) {
+            Some(workspace) => workspace.layout.name().to_string(),
+            None => return false,
+        };
+        self.layouts
+            .lock()
+            .unwrap()
+            .get(&layout)
+            .is_some_and(|layout| layout.depends_on_focus())
+    }
+
+    /// Focuses the next window of the active workspace, wrapping around
+    pub fn focus_next(&self) -> Result<String, String> {
+        self.cycle_focus(Workspace::focus_next)
+    }
+
+    /// Focuses the previous window of the active workspace, wrapping around
+    pub fn focus_prev(&self) -> Result<String, String> {
+        self.cycle_focus(Workspace::focus_prev)
+    }
+
+    fn cycle_focus(&self, cycle: fn(&mut Workspace) -> Option<String>) -> Result<String, String> {
+        // Cycles through what is shown, including sticky windows from elsewhere;
+        // `focus_window` records the result on the real workspace
+        let mut workspace = self.shown_snapshot(&self.get_active_workspace())?;
+        let window_id = cycle(&mut workspace).ok_or("No windows to focus")?;
+
+        self.focus_window(&window_id)?;
+        Ok(window_id)
+    }
+
+    /// Finds the focused window of the active workspace and its nearest
+    /// neighbor in `direction`, based on the currently arranged rectangles
+    fn directional_neighbor(&self, direction: Direction) -> Result<(String, String, String), String> {
+        let active_workspace = self.get_active_workspace();
+        let focused = self.workspaces
+            .lock()
+            .unwrap()
+            .get(&active_workspace)
+            .ok_or("Workspace not found")?
+            .focused_window
+            .clone()
+            .ok_or("No focused window")?;
+        // Only windows `arrange_workspace` laid out have current rects;
+        // floating ones keep wherever they were put
+        let window_ids = self.tiled_snapshot(&active_workspace)?.windows;
+
+        let windows = self.windows.lock().unwrap();
+        let from = windows.get(&focused).map(ManagedWindow::rect).ok_or("Window not found")?;
+        let candidates: Vec<(String, Rect)> = window_ids
+            .iter()
+            .filter(|id| **id != focused)
+            .filter_map(|id| windows.get(id))
+            .filter(|w| !matches!(w.state, WindowState::Minimized))
+            .map(|w| (w.id.clone(), w.rect()))
+            .collect();
+        drop(windows);
+
+        let neighbor = neighbor_in_direction(from, &candidates, direction).ok_or("No window in that direction")?;
+        Ok((active_workspace, focused, neighbor))
+    }
+
+    /// Focuses the nearest window in `direction` on the active workspace
+    pub fn focus_direction(&self, direction: Direction) -> Result<String, String> {
+        let (_, _, neighbor) = self.directional_neighbor(direction)?;
+        self.focus_window(&neighbor)?;
+        Ok(neighbor)
+    }
+
+    /// Swaps the focused window with its nearest neighbor in `direction`;
+    /// focus stays with the moved window
+    pub fn swap_direction(&self, direction: Direction) -> Result<(), String> {
+        let (workspace_id, focused, neighbor) = self.directional_neighbor(direction)?;
+
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let workspace = workspaces.get_mut(&workspace_id).ok_or("Workspace not found")?;
+            // Visiting sticky windows are always tiled after the workspace's own
+            if ![&focused, &neighbor].iter().all(|id| workspace.windows.contains(id)) {
+                return Err("Sticky windows from other workspaces cannot be swapped".to_string());
+            }
+            workspace.swap_windows(&focused, &neighbor);
+        }
+
+        self.arrange_workspace(&workspace_id)
+    }
+
+    /// Lays out the tiled windows of a workspace. A vetoed arrangement is
+    /// skipped rather than failed, as callers have already changed the state
+    /// being arranged.
+    pub fn arrange_workspace(&self, workspace_id: &str) -> Result<(), String> {
+        let mut workspace = self.tiled_snapshot(workspace_id)?;
+        if let Err(reason) = self.run_before_hooks(|h| &h.before_arrange, &mut workspace) {
+            log::debug!("Arranging workspace {} was vetoed: {}", workspace_id, reason);
+            return Ok(());
+        }
+        let rects = self.compute_layout(&workspace)?;
+
+        // Adopted windows, e.g. one that swallowed a terminal, are moved into their tiles
+        let mut bounds = Vec::new();
+        {
+            let mut windows = self.windows.lock().unwrap();
+            for (window_id, rect) in &rects {
+                if let Some(window) = windows.get_mut(window_id) {
+                    window.position = WindowPosition { x: rect.x, y: rect.y };
+                    window.size = WindowSize {
+                        width: rect.width,
+                        height: rect.height,
+                    };
+                    if let Some(handle) = window.handle.filter(|_| !matches!(window.state, WindowState::Minimized)) {
+                        bounds.push(WindowBounds { handle, x: rect.x, y: rect.y, width: rect.width, height: rect.height });
+                    }
+                }
+            }
+        }
+        if !bounds.is_empty() {
+            // A window that just went away must not keep the rest from being arranged
+            if let Err(e) = self.set_system_windows_bounds(&bounds) {
+                log::warn!("Failed to move adopted windows into their tiles: {}", e);
+            }
+        }
+
+        let arranged = WindowsArranged { workspace_id: workspace_id.to_string(), positions: rects };
+        self.emit(events::WINDOWS_ARRANGED, &arranged);
+        self.run_after_hooks(|h| &h.after_arrange, &arranged);
+        Ok(())
+    }
+
+    /// A copy of the workspace holding exactly the windows it tiles, so custom
+    /// layouts never run with the lock held. Sticky windows join the layout of
+    /// whichever workspace is active and leave their own while it is not.
+    /// Floating windows drop out of the snapshot only, so they get their tile
+    /// back once they stop floating.
+    fn tiled_snapshot(&self, workspace_id: &str) -> Result<Workspace, String> {
+        self.snapshot_without(workspace_id, |w| w.floating)
+    }
+
+    /// A copy of the workspace holding the windows shown on it: its own and,
+    /// while it is active, sticky windows from elsewhere
+    fn shown_snapshot(&self, workspace_id: &str) -> Result<Workspace, String> {
+        self.snapshot_without(workspace_id, |_| false)
+    }
+
+    fn snapshot_without(&self, workspace_id: &str, exclude: fn(&ManagedWindow) -> bool) -> Result<Workspace, String> {
+        let mut workspace = self.workspaces
+            .lock()
+            .unwrap()
+            .get(workspace_id)
+            .cloned()
+            .ok_or("Workspace not found")?;
+
+        let active = self.get_active_workspace() == workspace_id;
+        if active {
+            // A preselected split is meant for the workspace's next own window
+            workspace.bsp_preselect = None;
+            for window_id in self.sticky_windows_outside(workspace_id) {
+                workspace.add_window_unfocused(window_id);
+            }
+        }
+
+        let excluded: Vec<String> = {
+            let windows = self.windows.lock().unwrap();
+            workspace.windows
+                .iter()
+                .filter(|id| {
+                    windows.get(*id)
+                        .map(|w| exclude(w) || (w.sticky && !active))
+                        .unwrap_or(false)
+                })
+                .cloned()
+                .collect()
+        };
+        for window_id in &excluded {
+            workspace.remove_window(window_id);
+        }
+        Ok(workspace)
+    }
+
+    /// Ids of sticky windows that belong to a workspace other than `workspace_id`,
+    /// oldest first
+    fn sticky_windows_outside(&self, workspace_id: &str) -> Vec<String> {
+        let windows = self.windows.lock().unwrap();
+        let mut sticky: Vec<&ManagedWindow> = windows
+            .values()
+            .filter(|w| w.sticky && w.workspace_id != workspace_id && w.workspace_id != SCRATCHPAD_WORKSPACE_ID)
+            .collect();
+        sticky.sort_by_key(|w| w.created_at);
+        sticky.into_iter().map(|w| w.id.clone()).collect()
+    }
+
+    /// Flips the sticky flag of a window and re-arranges the active workspace.
+    /// Returns the new flag.
+    pub fn toggle_sticky(&self, window_id: &str) -> Result<bool, String> {
+        let sticky = {
+            let mut windows = self.windows.lock().unwrap();
+            let window = windows.get_mut(window_id).ok_or("Window not found")?;
+            window.sticky = !window.sticky;
+            window.sticky
+        };
+
+        self.arrange_workspace(&self.get_active_workspace())?;
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+        Ok(sticky)
+    }
+
+    /// Flips the floating flag of a window and re-arranges its workspace.
+    /// Returns the new flag.
+    pub fn toggle_floating(&self, window_id: &str) -> Result<bool, String> {
+        let (floating, workspace_id) = {
+            let mut windows = self.windows.lock().unwrap();
+            let window = windows.get_mut(window_id).ok_or("Window not found")?;
+            window.floating = !window.floating;
+            (window.floating, window.workspace_id.clone())
+        };
+
+        if self.workspaces.lock().unwrap().contains_key(&workspace_id) {
+            self.arrange_workspace(&workspace_id)?;
+        }
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+        Ok(floating)
+    }
+
+    /// Centers a floating window on the screen, keeping its size
+    pub fn center_floating_window(&self, window_id: &str) -> Result<(), String> {
+        let workspace_id = self.windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| w.workspace_id.clone())
+            .ok_or("Window not found")?;
+        let screen = self.get_workspace(&workspace_id)
+            .map(|workspace| self.workspace_area(&workspace))
+            .unwrap_or_else(|| self.screen_area());
+
+        let mut windows = self.windows.lock().unwrap();
+        let window = windows.get_mut(window_id).ok_or("Window not found")?;
+
+        if !window.floating {
+            return Err("Window is not floating".to_string());
+        }
+
+        window.position = WindowPosition {
+            x: screen.x + (screen.width as i32 - window.size.width as i32) / 2,
+            y: screen.y + (screen.height as i32 - window.size.height as i32) / 2,
+        };
+        drop(windows);
+
+        self.emit_window(events::WINDOW_UPDATED, window_id);
+        Ok(())
+    }
+
+    /// Re-enumerates monitors from the platform. When it reports none, a
+    /// single monitor sized from the config stands in.
+    pub fn get_monitors(&self) -> Result<Vec<Monitor>, String> {
+        let monitors = (self.monitor_source)()?;
+        *self.monitors.lock().unwrap() = Some(monitors);
+        Ok(self.cached_monitors())
+    }
+
+    /// Monitors from the last query, querying once if there was none yet
+    fn cached_monitors(&self) -> Vec<Monitor> {
+        let queried = self.monitors.lock().unwrap().clone();
+        let monitors = match queried {
+            Some(monitors) => monitors,
+            None => {
+                let monitors = (self.monitor_source)().unwrap_or_else(|e| {
+                    log::warn!("Failed to enumerate monitors: {}", e);
+                    Vec::new()
+                });
+                *self.monitors.lock().unwrap() = Some(monitors.clone());
+                monitors
+            }
+        };
+
+        if monitors.is_empty() {
+            vec![Monitor::from_config(&self.config.lock().unwrap())]
+        } else {
+            monitors
+        }
+    }
+
+    /// Drops the cached monitors, so they and the space panels reserve are
+    /// queried again the next time a workspace is arranged
+    pub fn invalidate_monitors(&self) {
+        *self.monitors.lock().unwrap() = None;
+    }
+
+    /// Re-arranges the active workspace for a changed work area, e.g. after a
+    /// panel started or quit
+    pub fn refresh_work_area(&self) -> Result<(), String> {
+        self.invalidate_monitors();
+        self.arrange_workspace(&self.get_active_workspace())
+    }
+
+    /// Binds a workspace to a monitor, or to the primary monitor with `None`,
+    /// and re-arranges it there
+    pub fn set_workspace_monitor(&self, workspace_id: &str, monitor_id: Option<String>) -> Result<(), String> {
+        if let Some(monitor_id) = &monitor_id {
+            if !self.cached_monitors().iter().any(|m| &m.id == monitor_id) {
+                return Err("Monitor not found".to_string());
+            }
+        }
+
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .monitor = monitor_id;
+
+        self.arrange_workspace(workspace_id)?;
+        self.emit_workspace(events::WORKSPACE_UPDATED, workspace_id);
+        Ok(())
+    }
+
+    /// Keeps `strut` free on the monitor with `monitor_id` (the primary one
+    /// with `None`), e.g. for a bar the app renders, and re-arranges the
+    /// workspaces there. An empty strut releases the space again.
+    pub fn reserve_strut(&self, monitor_id: Option<String>, strut: Strut) -> Result<(), String> {
+        let monitors = self.cached_monitors();
+        let monitor = match &monitor_id {
+            Some(id) => monitors.iter().find(|m| &m.id == id),
+            None => monitors.iter().find(|m| m.primary).or_else(|| monitors.first()),
+        }
+        .ok_or("Monitor not found")?;
+        let primary = monitor.primary;
+        let monitor_id = monitor.id.clone();
+
+        {
+            let mut reserved_struts = self.reserved_struts.lock().unwrap();
+            if strut.is_empty() {
+                reserved_struts.remove(&monitor_id);
+            } else {
+                reserved_struts.insert(monitor_id.clone(), strut);
+            }
+        }
+
+        let workspace_ids: Vec<String> = self.workspaces
+            .lock()
+            .unwrap()
+            .values()
+            .filter(|ws| match &ws.monitor {
+                Some(id) => *id == monitor_id || (primary && !monitors.iter().any(|m| &m.id == id)),
+                None => primary,
+            })
+            .map(|ws| ws.id.clone())
+            .collect();
+        for workspace_id in workspace_ids {
+            self.arrange_workspace(&workspace_id)?;
+        }
+        Ok(())
+    }
+
+    /// Work area of the monitor with `monitor_id`, less the space reserved by
+    /// the app, falling back to the primary monitor when it is `None` or no
+    /// longer connected
+    fn monitor_area(&self, monitor_id: Option<&str>) -> Rect {
+        let monitors = self.cached_monitors();
+        let monitor = monitor_id
+            .and_then(|id| monitors.iter().find(|m| m.id == id))
+            .or_else(|| monitors.iter().find(|m| m.primary))
+            .or_else(|| monitors.first());
+        match monitor {
+            Some(monitor) => match self.reserved_struts.lock().unwrap().get(&monitor.id) {
+                Some(strut) => strut.apply(monitor.work_area),
+                None => monitor.work_area,
+            },
+            None => Rect { x: 0, y: 0, width: 0, height: 0 },
+        }
+    }
+
+    /// Area of the primary monitor
+    fn screen_area(&self) -> Rect {
+        self.monitor_area(None)
+    }
+
+    fn workspace_area(&self, workspace: &Workspace) -> Rect {
+        self.monitor_area(workspace.monitor.as_deref())
+    }
+
+    fn compute_layout(&self, workspace: &Workspace) -> Result<Vec<(String, Rect)>, String> {
+        let layout = self.layouts
+            .lock()
+            .unwrap()
+            .get(workspace.layout.name())
+            .cloned()
+            .ok_or_else(|| format!("Layout '{}' is not registered", workspace.layout.name()))?;
+
+        let area = self.workspace_area(workspace);
+        let gap = self.config.lock().unwrap().window_gap;
+
+        Ok(layout.arrange(area, workspace.get_windows(), gap, workspace))
+    }
+
+    fn tab_info(workspace: &Workspace, area: Rect, titles: &HashMap<String, String>) -> Vec<TabInfo> {
+        tab_headers(area, workspace.get_windows(), workspace)
+            .into_iter()
+            .enumerate()
+            .map(|(index, (window_id, header))| TabInfo {
+                title: titles.get(&window_id).cloned().unwrap_or_default(),
+                active: workspace.focused_window.as_ref() == Some(&window_id),
+                window_id,
+                index,
+                header,
+            })
+            .collect()
+    }
+
+    /// Tab bar metadata for a workspace using the tabbed or stacked layout
+    pub fn get_tabs(&self, workspace_id: &str) -> Result<Vec<TabInfo>, String> {
+        // One tab per window the layout arranges
+        let workspace = self.tiled_snapshot(workspace_id)?;
+
+        let titles: HashMap<String, String> = self.windows
+            .lock()
+            .unwrap()
+            .values()
+            .map(|w| (w.id.clone(), w.title.clone()))
+            .collect();
+
+        Ok(Self::tab_info(&workspace, self.workspace_area(&workspace), &titles))
+    }
+
+    pub fn register_layout(&self, name: &str, layout: Arc<dyn Layout>) {
+        self.layouts.lock().unwrap().insert(name.to_string(), layout);
+    }
+
+    pub fn has_layout(&self, name: &str) -> bool {
+        self.layouts.lock().unwrap().contains_key(name)
+    }
+
+    pub fn get_layout_names(&self) -> Vec<String> {
+        let mut names: Vec<String> = self.layouts.lock().unwrap().keys().cloned().collect();
+        names.sort();
+        names
+    }
+
+    pub fn set_layout_option(&self, workspace_id: &str, key: String, value: serde_json::Value) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .layout_options
+            .insert(key, value);
+
+        self.arrange_workspace(workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, workspace_id);
+        Ok(())
+    }
+
+    /// Moves the edge of a tile in `direction` by `delta` pixels, growing the
+    /// tile and shrinking its neighbor on that side. The resulting weights are
+    /// kept on the workspace so later arrangements preserve them.
+    pub fn resize_tile(&self, window_id: &str, direction: Direction, delta: i32) -> Result<(), String> {
+        let (workspace_id, floating, sticky) = self.windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| (w.workspace_id.clone(), w.floating, w.sticky))
+            .ok_or("Window not found")?;
+
+        if floating {
+            return Err("Floating windows are not tiled".to_string());
+        }
+        // Sticky windows are tiled on whichever workspace is active
+        let workspace_id = if sticky { self.get_active_workspace() } else { workspace_id };
+
+        // The grid must match what `arrange_workspace` tiles, and a custom
+        // layout never sees the lock held
+        let workspace = self.tiled_snapshot(&workspace_id)?;
+
+        if !matches!(workspace.layout, LayoutType::Tiling) {
+            return Err("Tiles can only be resized in the tiling layout".to_string());
+        }
+
+        let rects: HashMap<String, Rect> = self.compute_layout(&workspace)?.into_iter().collect();
+        // A layout registered over "tiling" may leave windows out
+        let tile = |id: &str| rects.get(id).copied().ok_or_else(|| format!("Layout did not place window {}", id));
+        let count = workspace.windows.len();
+        let index = workspace.windows
+            .iter()
+            .position(|id| id == window_id)
+            .ok_or("Window not found")?;
+        let (cols, _) = tiling_grid(count);
+        let (row, col) = (index / cols, index % cols);
+
+        // Width and height factors for each tile whose weight changes
+        let mut scales: Vec<(String, f32, f32)> = Vec::new();
+        match direction {
+            Direction::Left | Direction::Right => {
+                let neighbor_col = match direction {
+                    Direction::Left => col.checked_sub(1),
+                    _ => Some(col + 1).filter(|c| *c < cols),
+                };
+                let neighbor_index = neighbor_col
+                    .map(|c| row * cols + c)
+                    .filter(|i| *i < count)
+                    .ok_or("No neighboring tile in that direction")?;
+                let neighbor_id = workspace.windows[neighbor_index].clone();
+
+                let width = tile(window_id)?.width as i32;
+                let neighbor_width = tile(&neighbor_id)?.width as i32;
+                let delta = Self::clamp_resize(delta, width, neighbor_width)?;
+
+                scales.push((window_id.to_string(), (width + delta) as f32 / width as f32, 1.0));
+                scales.push((neighbor_id, (neighbor_width - delta) as f32 / neighbor_width as f32, 1.0));
+            }
+            Direction::Up | Direction::Down => {
+                let neighbor_row = match direction {
+                    Direction::Up => row.checked_sub(1),
+                    _ => Some(row + 1).filter(|r| r * cols < count),
+                }
+                .ok_or("No neighboring tile in that direction")?;
+
+                let row_ids: Vec<String> = workspace.windows.chunks(cols).nth(row).unwrap_or_default().to_vec();
+                let neighbor_ids: Vec<String> = workspace.windows.chunks(cols).nth(neighbor_row).unwrap_or_default().to_vec();
+
+                let height = tile(window_id)?.height as i32;
+                let neighbor_height = tile(&neighbor_ids[0])?.height as i32;
+                let delta = Self::clamp_resize(delta, height, neighbor_height)?;
+
+                // Row heights follow the average weight, so scale the whole row
+                for id in row_ids {
+                    scales.push((id, 1.0, (height + delta) as f32 / height as f32));
+                }
+                for id in neighbor_ids {
+                    scales.push((id, 1.0, (neighbor_height - delta) as f32 / neighbor_height as f32));
+                }
+            }
+        }
+
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let workspace = workspaces.get_mut(&workspace_id).ok_or("Workspace not found")?;
+            for (id, width_factor, height_factor) in &scales {
+                workspace.scale_tile_weight(id, *width_factor, *height_factor);
+            }
+        }
+
+        self.arrange_workspace(&workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, &workspace_id);
+        Ok(())
+    }
+
+    /// Limits `delta` so neither of two adjacent tiles gets smaller than
+    /// `MIN_TILE_SIZE`
+    fn clamp_resize(delta: i32, size: i32, neighbor_size: i32) -> Result<i32, String> {
+        let min = MIN_TILE_SIZE as i32;
+        if size <= 0 || neighbor_size <= 0 || size + neighbor_size < 2 * min {
+            return Err("Tiles are too small to resize".to_string());
+        }
+        Ok(delta.clamp(min - size, neighbor_size - min))
+    }
+
+    pub fn reset_tile_weights(&self, workspace_id: &str) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .tile_weights
+            .clear();
+
+        self.arrange_workspace(workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, workspace_id);
+        Ok(())
+    }
+
+    pub fn adjust_master_ratio(&self, workspace_id: &str, delta: f32) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .adjust_master_ratio(delta);
+
+        self.arrange_workspace(workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, workspace_id);
+        Ok(())
+    }
+
+    pub fn adjust_master_count(&self, workspace_id: &str, delta: i32) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .adjust_master_count(delta);
+
+        self.arrange_workspace(workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, workspace_id);
+        Ok(())
+    }
+
+    /// Grows the BSP tile of a window by `delta` of the split it shares with
+    /// its sibling, e.g. 0.05 for five percent, shrinking the sibling
+    pub fn resize_bsp_split(&self, window_id: &str, delta: f32) -> Result<(), String> {
+        self.edit_bsp_split(window_id, |tree| tree.resize_split(window_id, delta))
+    }
+
+    /// Turns the BSP split a window shares with its sibling between side by
+    /// side and stacked
+    pub fn rotate_bsp_split(&self, window_id: &str) -> Result<(), String> {
+        self.edit_bsp_split(window_id, |tree| tree.rotate_split(window_id))
+    }
+
+    fn edit_bsp_split(&self, window_id: &str, edit: impl FnOnce(&mut BspNode) -> bool) -> Result<(), String> {
+        let (workspace_id, floating) = self.windows
+            .lock()
+            .unwrap()
+            .get(window_id)
+            .map(|w| (w.workspace_id.clone(), w.floating))
+            .ok_or("Window not found")?;
+
+        if floating {
+            return Err("Floating windows are not tiled".to_string());
+        }
+
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let workspace = workspaces.get_mut(&workspace_id).ok_or("Workspace not found")?;
+            if !matches!(workspace.layout, LayoutType::Bsp) {
+                return Err("Splits can only be changed in the BSP layout".to_string());
+            }
+            if !workspace.bsp_tree.as_mut().is_some_and(edit) {
+                return Err("Window does not share a split".to_string());
+            }
+        }
+
+        self.arrange_workspace(&workspace_id)?;
+        self.emit_workspace(events::LAYOUT_CHANGED, &workspace_id);
+        Ok(())
+    }
+
+    /// Makes the next window added to a workspace split its leaf in
+    /// `direction`; `None` goes back to alternating with depth
+    pub fn preselect_bsp_split(&self, workspace_id: &str, direction: Option<SplitDirection>) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .bsp_preselect = direction;
+
+        self.emit_workspace(events::LAYOUT_CHANGED, workspace_id);
+        Ok(())
+    }
+
+    pub fn create_workspace(&self, name: String, layout: LayoutType) -> String {
+        let mut workspace = Workspace::new(name, layout);
+        let workspace_id = workspace.id.clone();
+        
+        {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            workspace.index = workspaces.len();
+            workspaces.insert(workspace_id.clone(), workspace.clone());
+        }
+
+        self.emit(events::WORKSPACE_CREATED, &workspace);
+        workspace_id
+    }
+
+    pub fn switch_workspace(&self, workspace_id: &str) -> Result<(), String> {
+        let mut workspace_id = workspace_id.to_string();
+        self.run_before_hooks(|h| &h.before_workspace_switch, &mut workspace_id)?;
+        let workspace_id = workspace_id.as_str();
+
+        if self.workspaces.lock().unwrap().contains_key(workspace_id) {
+            let previous = std::mem::replace(&mut *self.active_workspace.lock().unwrap(), workspace_id.to_string());
+
+            // Sticky windows leave the previous workspace's layout, giving
+            // their tiles back, and join the new one's
+            let has_sticky = self.windows
+                .lock()
+                .unwrap()
+                .values()
+                .any(|w| w.sticky && w.workspace_id != SCRATCHPAD_WORKSPACE_ID);
+            if has_sticky && previous != workspace_id {
+                if self.workspaces.lock().unwrap().contains_key(&previous) {
+                    self.arrange_workspace(&previous)?;
+                }
+                self.arrange_workspace(workspace_id)?;
+            }
+
+            let switched = WorkspaceSwitched { workspace_id: workspace_id.to_string(), previous };
+            self.emit(events::WORKSPACE_SWITCHED, &switched);
+            self.run_after_hooks(|h| &h.after_workspace_switch, &switched);
+            Ok(())
+        } else {
+            Err("Workspace not found".to_string())
+        }
+    }
+
+    /// Switches to the workspace at 1-based position `number`, as used by the
+    /// `switch_workspace_N` keybindings. Returns the workspace id.
+    pub fn switch_workspace_by_number(&self, number: usize) -> Result<String, String> {
+        let workspace_id = self.workspaces
+            .lock()
+            .unwrap()
+            .iter()
+            .find(|(_, workspace)| workspace.index + 1 == number)
+            .map(|(id, _)| id.clone())
+            .ok_or("Workspace not found")?;
+
+        self.switch_workspace(&workspace_id)?;
+        Ok(workspace_id)
+    }
+
+    pub fn get_workspace(&self, workspace_id: &str) -> Option<Workspace> {
+        self.workspaces.lock().unwrap().get(workspace_id).cloned()
+    }
+
+    /// Looks up a workspace by name; if several share the name the one with
+    /// the lowest index wins
+    pub fn get_workspace_by_name(&self, name: &str) -> Option<Workspace> {
+        self.get_workspaces().into_iter().find(|w| w.name == name)
+    }
+
+    pub fn rename_workspace(&self, workspace_id: &str, name: String) -> Result<(), String> {
+        self.workspaces
+            .lock()
+            .unwrap()
+            .get_mut(workspace_id)
+            .ok_or("Workspace not found")?
+            .name = name;
+
+        self.emit_workspace(events::WORKSPACE_UPDATED, workspace_id);
+        Ok(())
+    }
+
+    /// Moves a workspace to zero-based position `index`, shifting the others
+    pub fn move_workspace(&self, workspace_id: &str, index: usize) -> Result<(), String> {
+        let mut workspaces = self.workspaces.lock().unwrap();
+        if !workspaces.contains_key(workspace_id) {
+            return Err("Workspace not found".to_string());
+        }
+
+        let mut order = Self::workspace_order(&workspaces);
+        order.retain(|id| id != workspace_id);
+        order.insert(index.min(order.len()), workspace_id.to_string());
+        Self::reindex_workspaces(&mut workspaces, &order);
+        drop(workspaces);
+
+        self.emit(events::WORKSPACES_REORDERED, &order);
+        Ok(())
+    }
+
+    /// Deletes a workspace, handling its windows according to `policy`. The
+    /// last remaining workspace cannot be deleted. If the deleted workspace was
+    /// active, the migration target (or the first workspace) becomes active
+    /// through the workspace switch hooks, which may veto the deletion.
+    pub fn delete_workspace(&self, workspace_id: &str, policy: OrphanPolicy) -> Result<(), String> {
+        let target = {
+            let workspaces = self.workspaces.lock().unwrap();
+            if !workspaces.contains_key(workspace_id) {
+                return Err("Workspace not found".to_string());
+            }
+            if workspaces.len() == 1 {
+                return Err("Cannot delete the last workspace".to_string());
+            }
+
+            match &policy {
+                OrphanPolicy::Migrate(Some(target)) => {
+                    if target == workspace_id || !workspaces.contains_key(target) {
+                        return Err("Target workspace not found".to_string());
+                    }
+                    target.clone()
+                }
+                _ => Self::workspace_order(&workspaces)
+                    .into_iter()
+                    .find(|id| id != workspace_id)
+                    .unwrap_or_default(),
             }
-            
-            Ok(())
+        };
+
+        // Switch hooks run before anything changes, so a veto leaves the
+        // workspace and its windows in place
+        let switch_to = if self.get_active_workspace() == workspace_id {
+            let mut switch_to = target.clone();
+            self.run_before_hooks(|h| &h.before_workspace_switch, &mut switch_to)?;
+            if switch_to == workspace_id || !self.workspaces.lock().unwrap().contains_key(&switch_to) {
+                return Err("Workspace not found".to_string());
+            }
+            Some(switch_to)
         } else {
-            Err("Window not found".to_string())
-        }
-    }
+            None
+        };
 
-    pub fn arrange_workspace(&self, workspace_id: &str) -> Result<(), String> {
-        let workspace = self.workspaces.lock().unwrap();
-        let workspace = workspace.get(workspace_id).ok_or("Workspace not found")?;
-        
-        let window_ids = workspace.get_windows();
-        let layout = workspace.layout.clone();
-        
-        drop(workspace);
+        // Windows are closed while their workspace still exists, so removal
+        // hooks can veto and swallowed terminals have a slot to return to
+        if let OrphanPolicy::Close = policy {
+            self.close_workspace_windows(workspace_id)?;
+        }
 
-        let config = self.config.lock().unwrap();
-        let screen_width = config.screen_width;
-        let screen_height = config.screen_height;
-        let gap = config.window_gap;
-        drop(config);
+        let removed = {
+            let mut workspaces = self.workspaces.lock().unwrap();
+            let removed = workspaces.remove(workspace_id).ok_or("Workspace not found")?;
+            let order = Self::workspace_order(&workspaces);
+            Self::reindex_workspaces(&mut workspaces, &order);
+            removed
+        };
 
-        match layout {
-            LayoutType::Tiling => self.arrange_tiling(&window_ids, screen_width, screen_height, gap),
-            LayoutType::Floating => Ok(()),
-            LayoutType::Monocle => self.arrange_monocle(&window_ids, screen_width, screen_height),
+        if let OrphanPolicy::Migrate(_) = policy {
+            let mut windows = self.windows.lock().unwrap();
+            let mut workspaces = self.workspaces.lock().unwrap();
+            // Including terminals hidden by a swallowing window
+            for window in windows.values_mut().filter(|w| w.workspace_id == workspace_id) {
+                window.workspace_id = target.clone();
+            }
+            if let Some(workspace) = workspaces.get_mut(&target) {
+                for window_id in removed.get_windows() {
+                    workspace.add_window_unfocused(window_id.clone());
+                }
+            }
         }
-    }
 
-    fn arrange_tiling(&self, window_ids: &[String], screen_width: u32, screen_height: u32, gap: u32) -> Result<(), String> {
-        if window_ids.is_empty() {
-            return Ok(());
+        if let Some(switch_to) = &switch_to {
+            *self.active_workspace.lock().unwrap() = switch_to.clone();
         }
 
-        let mut windows = self.windows.lock().unwrap();
-        let count = window_ids.len();
-        
-        if count == 1 {
-            if let Some(window) = windows.get_mut(&window_ids[0]) {
-                window.position = WindowPosition { x: gap as i32, y: gap as i32 };
-                window.size = WindowSize {
-                    width: screen_width - (gap * 2),
-                    height: screen_height - (gap * 2),
-                };
-            }
-        } else {
-            let cols = (count as f64).sqrt().ceil() as usize;
-            let rows = (count + cols - 1) / cols;
-            
-            let window_width = (screen_width - gap * (cols as u32 + 1)) / cols as u32;
-            let window_height = (screen_height - gap * (rows as u32 + 1)) / rows as u32;
+        self.arrange_workspace(&target)?;
+        if let Some(switch_to) = switch_to.as_ref().filter(|id| **id != target) {
+            self.arrange_workspace(switch_to)?;
+        }
 
-            for (i, window_id) in window_ids.iter().enumerate() {
-                if let Some(window) = windows.get_mut(window_id) {
-                    let col = i % cols;
-                    let row = i / cols;
-                    
-                    window.position = WindowPosition {
-                        x: (gap + col as u32 * (window_width + gap)) as i32,
-                        y: (gap + row as u32 * (window_height + gap)) as i32,
-                    };
-                    window.size = WindowSize {
-                        width: window_width,
-                        height: window_height,
-                    };
-                }
+        if let OrphanPolicy::Migrate(_) = policy {
+            for window_id in removed.get_windows() {
+                self.emit_window(events::WINDOW_UPDATED, window_id);
             }
         }
-
+        self.emit(events::WORKSPACE_DELETED, &WorkspaceDeleted { workspace_id: workspace_id.to_string() });
+        if let Some(switch_to) = switch_to {
+            let switched = WorkspaceSwitched { workspace_id: switch_to, previous: workspace_id.to_string() };
+            self.emit(events::WORKSPACE_SWITCHED, &switched);
+            self.run_after_hooks(|h| &h.after_workspace_switch, &switched);
+        }
         Ok(())
     }
 
-    fn arrange_monocle(&self, window_ids: &[String], screen_width: u32, screen_height: u32) -> Result<(), String> {
-        let mut windows = self.windows.lock().unwrap();
-        
-        for window_id in window_ids {
-            if let Some(window) = windows.get_mut(window_id) {
-                window.position = WindowPosition { x: 0, y: 0 };
-                window.size = WindowSize {
-                    width: screen_width,
-                    height: screen_height,
-                };
+    /// Removes every window of a workspace through `remove_window`, closing
+    /// adopted system windows as well. Terminals that come back when the
+    /// window swallowing them goes are closed in turn.
+    fn close_workspace_windows(&self, workspace_id: &str) -> Result<(), String> {
+        let mut attempted = HashSet::new();
+        loop {
+            let next = self.workspaces
+                .lock()
+                .unwrap()
+                .get(workspace_id)
+                .and_then(|workspace| workspace.windows.iter().find(|id| !attempted.contains(*id)).cloned());
+            let Some(window_id) = next else {
+                return Ok(());
+            };
+            attempted.insert(window_id.clone());
+
+            let handle = self.windows.lock().unwrap().get(&window_id).and_then(|w| w.handle);
+            self.remove_window(&window_id)?;
+            if let Some(handle) = handle {
+                if let Err(e) = self.close_system_window(handle) {
+                    log::warn!("Failed to close system window {}: {}", handle, e);
+                }
             }
         }
-
-        Ok(())
     }
 
-    pub fn create_workspace(&self, name: String, layout: LayoutType) -> String {
-        let workspace_id = Uuid::new_v4().to_string();
-        let workspace = Workspace::new(name, layout);
-        
-        self.workspaces.lock().unwrap().insert(workspace_id.clone(), workspace);
-        workspace_id
+    /// Workspace ids sorted by their index
+    fn workspace_order(workspaces: &HashMap<String, Workspace>) -> Vec<String> {
+        let mut order: Vec<(&String, usize)> = workspaces.iter().map(|(id, w)| (id, w.index)).collect();
+        order.sort_by_key(|(_, index)| *index);
+        order.into_iter().map(|(id, _)| id.clone()).collect()
     }
 
-    pub fn switch_workspace(&self, workspace_id: &str) -> Result<(), String> {
-        if self.workspaces.lock().unwrap().contains_key(workspace_id) {
-            *self.active_workspace.lock().unwrap() = workspace_id.to_string();
-            Ok(())
-        } else {
-            Err("Workspace not found".to_string())
+    fn reindex_workspaces(workspaces: &mut HashMap<String, Workspace>, order: &[String]) {
+        for (index, id) in order.iter().enumerate() {
+            if let Some(workspace) = workspaces.get_mut(id) {
+                workspace.index = index;
+            }
         }
     }
 
+    /// All workspaces, ordered by index
     pub fn get_workspaces(&self) -> Vec<Workspace> {
-        self.workspaces.lock().unwrap().values().cloned().collect()
+        let mut workspaces: Vec<Workspace> = self.workspaces.lock().unwrap().values().cloned().collect();
+        workspaces.sort_by_key(|w| w.index);
+        workspaces
+    }
+
+    /// Replaces the config, compiling the regexes of its rules
+    pub fn update_config(&self, mut config: Config) {
+        for rule in &mut config.rules {
+            rule.compile();
+        }
+        *self.config.lock().unwrap() = config.clone();
+        self.emit(events::CONFIG_UPDATED, &config);
     }
 
     pub fn get_active_workspace(&self) -> String {
@@ -250,9 +1663,68 @@ impl WindowManager {
         *self.system_windows.lock().unwrap() = windows.iter()
             .map(|w| (w.handle, w.clone()))
             .collect();
+        self.apply_system_window_rules(&windows);
         Ok(windows)
     }
 
+    /// Evaluates the configured rules for system windows seen for the first
+    /// time. System windows have no separate app name, so `app_name` patterns
+    /// are matched against the process name. Workspace and sticky actions only
+    /// apply to managed windows.
+    fn apply_system_window_rules(&self, windows: &[SystemWindow]) {
+        let rules = self.config.lock().unwrap().rules.clone();
+        let mut new_windows = Vec::new();
+
+        {
+            let mut seen = self.system_window_rules.lock().unwrap();
+            seen.retain(|handle, _| windows.iter().any(|w| w.handle == *handle));
+
+            for window in windows {
+                if seen.contains_key(&window.handle) {
+                    continue;
+                }
+
+                let actions = evaluate_rules(&rules, Some(&window.process_name), Some(&window.process_name), &window.title);
+                seen.insert(window.handle, actions.clone());
+                if !actions.is_empty() {
+                    new_windows.push((window.clone(), actions));
+                }
+            }
+        }
+
+        let screen = self.screen_area();
+        for (window, actions) in new_windows {
+            let width = actions.width.unwrap_or(window.width);
+            let height = actions.height.unwrap_or(window.height);
+
+            let result = if actions.start_minimized.unwrap_or(false) {
+                self.minimize_system_window(window.handle)
+            } else if actions.floating.unwrap_or(false) {
+                let x = screen.x + (screen.width as i32 - width as i32) / 2;
+                let y = screen.y + (screen.height as i32 - height as i32) / 2;
+                self.set_system_window_bounds(window.handle, x, y, width, height)
+            } else if actions.width.is_some() || actions.height.is_some() {
+                self.resize_system_window(window.handle, width, height)
+            } else {
+                Ok(())
+            };
+
+            if let Err(e) = result {
+                log::warn!("Failed to apply window rules to {}: {}", window.handle, e);
+            }
+        }
+    }
+
+    /// System windows that rules float or ignore stay out of tiling
+    fn is_system_window_tiled(&self, handle: u64) -> bool {
+        self.system_window_rules
+            .lock()
+            .unwrap()
+            .get(&handle)
+            .map(|actions| !actions.floating.unwrap_or(false) && !actions.ignore.unwrap_or(false))
+            .unwrap_or(true)
+    }
+
     pub fn move_system_window(&self, handle: u64, x: i32, y: i32) -> Result<(), String> {
         PlatformWindowManager::move_window(handle, x, y)?;
         
@@ -295,6 +1767,10 @@ impl WindowManager {
         PlatformWindowManager::restore_window(handle)
     }
 
+    pub fn set_system_window_state(&self, handle: u64, flag: WindowStateFlag, enabled: bool) -> Result<(), String> {
+        PlatformWindowManager::set_window_state(handle, flag, enabled)
+    }
+
     pub fn close_system_window(&self, handle: u64) -> Result<(), String> {
         PlatformWindowManager::close_window(handle)?;
         self.system_windows.lock().unwrap().remove(&handle);
@@ -314,46 +1790,84 @@ impl WindowManager {
     }
 
     pub fn arrange_system_windows(&self, window_handles: &[u64]) -> Result<(), String> {
-        let config = self.config.lock().unwrap();
-        let screen_width = config.screen_width;
-        let screen_height = config.screen_height;
-        let gap = config.window_gap;
-        drop(config);
+        self.arrange_system_windows_with_layout(window_handles, LayoutType::Tiling.name(), None)
+    }
 
-        if window_handles.is_empty() {
-            return Ok(());
+    /// Builds a throwaway workspace so system windows can use any registered
+    /// layout. It holds the windows that are tiled, leaving out those rules
+    /// float or ignore, and monitors are re-read for it since panels and
+    /// docks come and go.
+    fn system_workspace(&self, window_handles: &[u64], layout: &str, active_handle: Option<u64>) -> Workspace {
+        if let Err(e) = self.get_monitors() {
+            log::warn!("Failed to refresh monitors: {}", e);
         }
 
-        let count = window_handles.len();
-        
-        if count == 1 {
-            self.set_system_window_bounds(
-                window_handles[0],
-                gap as i32,
-                gap as i32,
-                screen_width - (gap * 2),
-                screen_height - (gap * 2)
-            )?;
-        } else {
-            let cols = (count as f64).sqrt().ceil() as usize;
-            let rows = (count + cols - 1) / cols;
-            
-            let window_width = (screen_width - gap * (cols as u32 + 1)) / cols as u32;
-            let window_height = (screen_height - gap * (rows as u32 + 1)) / rows as u32;
+        let mut workspace = Workspace::new("System".to_string(), LayoutType::from_name(layout));
+        for handle in window_handles.iter().filter(|handle| self.is_system_window_tiled(**handle)) {
+            workspace.add_window(handle.to_string());
+        }
+        if let Some(handle) = active_handle {
+            workspace.focus_window(&handle.to_string());
+        }
+        workspace
+    }
+
+    pub fn arrange_system_windows_with_layout(
+        &self,
+        window_handles: &[u64],
+        layout: &str,
+        active_handle: Option<u64>,
+    ) -> Result<(), String> {
+        let workspace = self.system_workspace(window_handles, layout, active_handle);
 
-            for (i, &handle) in window_handles.iter().enumerate() {
-                let col = i % cols;
-                let row = i / cols;
-                
-                let x = (gap + col as u32 * (window_width + gap)) as i32;
-                let y = (gap + row as u32 * (window_height + gap)) as i32;
-                
-                self.set_system_window_bounds(handle, x, y, window_width, window_height)?;
+        let bounds = self.compute_layout(&workspace)?
+            .into_iter()
+            .map(|(window_id, rect)| {
+                let handle = window_id.parse::<u64>().map_err(|e| e.to_string())?;
+                Ok(WindowBounds { handle, x: rect.x, y: rect.y, width: rect.width, height: rect.height })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        self.set_system_windows_bounds(&bounds)
+    }
+
+    /// Moves and resizes native windows, keeping the cached copies in step
+    fn set_system_windows_bounds(&self, bounds: &[WindowBounds]) -> Result<(), String> {
+        // One batch so the backend can send every request with a single flush
+        PlatformWindowManager::set_windows_bounds(bounds)?;
+
+        let mut system_windows = self.system_windows.lock().unwrap();
+        for b in bounds {
+            if let Some(window) = system_windows.get_mut(&b.handle) {
+                window.x = b.x;
+                window.y = b.y;
+                window.width = b.width;
+                window.height = b.height;
             }
         }
 
         Ok(())
     }
+
+    /// Tab bar metadata for system windows arranged with the tabbed or stacked
+    /// layout; `window_id` holds the window handle
+    pub fn get_system_window_tabs(
+        &self,
+        window_handles: &[u64],
+        layout: &str,
+        active_handle: Option<u64>,
+    ) -> Vec<TabInfo> {
+        let workspace = self.system_workspace(window_handles, layout, active_handle);
+
+        let titles: HashMap<String, String> = self.system_windows
+            .lock()
+            .unwrap()
+            .values()
+            .map(|w| (w.handle.to_string(), w.title.clone()))
+            .collect();
+
+        Self::tab_info(&workspace, self.workspace_area(&workspace), &titles)
+    }
 }
 
 // === Tauri integration part ===
@@ -381,3 +1895,708 @@ pub fn init() -> tauri::plugin::TauriPlugin {
         ])
         .build()
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A manager on the single monitor sized from the config, so arranging
+    /// never queries the platform the tests run on
+    fn window_manager() -> WindowManager {
+        let mut wm = WindowManager::new();
+        wm.monitor_source = || Ok(Vec::new());
+        wm
+    }
+
+    #[test]
+    fn created_workspace_id_switches_and_arranges() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+
+        wm.switch_workspace(&workspace_id).unwrap();
+        assert_eq!(wm.get_active_workspace(), workspace_id);
+
+        let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.arrange_workspace(&workspace_id).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].workspace_id, workspace_id);
+    }
+
+    #[test]
+    fn workspace_ids_match_map_keys() {
+        let wm = window_manager();
+        wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+        wm.create_workspace("Web".to_string(), LayoutType::Monocle);
+
+        let workspaces = wm.get_workspaces();
+        assert_eq!(workspaces.len(), 3);
+        let map = wm.workspaces.lock().unwrap();
+        for (index, workspace) in workspaces.iter().enumerate() {
+            assert_eq!(workspace.index, index);
+            assert_eq!(map[&workspace.id].id, workspace.id);
+        }
+    }
+
+    #[test]
+    fn event_sink_may_call_back_into_the_manager() {
+        let wm = Arc::new(window_manager());
+        let received = Arc::new(Mutex::new(Vec::new()));
+
+        let (sink_wm, sink_received) = (Arc::downgrade(&wm), received.clone());
+        *wm.event_sink.lock().unwrap() = Some(Arc::new(move |event: &str, payload: serde_json::Value| {
+            sink_received.lock().unwrap().push(event.to_string());
+            if event == events::WINDOW_ADDED {
+                let window_id = payload["id"].as_str().unwrap().to_string();
+                sink_wm.upgrade().unwrap().focus_window(&window_id).unwrap();
+            }
+        }));
+
+        let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.minimize_window(&window_id).unwrap();
+
+        let received = received.lock().unwrap();
+        assert!(received.iter().any(|e| e == events::WINDOW_FOCUSED));
+        assert_eq!(received.last().map(String::as_str), Some(events::WINDOW_UPDATED));
+    }
+
+    #[test]
+    fn sticky_window_is_tiled_only_on_the_active_workspace() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let sticky = wm.add_window("Player".to_string(), "player".to_string()).unwrap();
+        wm.toggle_sticky(&sticky).unwrap();
+        let home_rect = wm.windows.lock().unwrap()[&sticky].rect();
+
+        let other = wm.create_workspace("Other".to_string(), LayoutType::Bsp);
+        wm.switch_workspace(&other).unwrap();
+        wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+
+        // The BSP layout splits the active workspace with the visiting window
+        let visiting_rect = wm.windows.lock().unwrap()[&sticky].rect();
+        assert_ne!(visiting_rect, home_rect);
+
+        // Arranging its inactive home workspace leaves it where it is shown
+        wm.arrange_workspace(&home).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&sticky].rect(), visiting_rect);
+    }
+
+    #[test]
+    fn switching_away_gives_sticky_tiles_back() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let editor = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        let full = wm.windows.lock().unwrap()[&editor].rect();
+
+        let other = wm.create_workspace("Other".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&other).unwrap();
+        let sticky = wm.add_window("Player".to_string(), "player".to_string()).unwrap();
+        wm.toggle_sticky(&sticky).unwrap();
+
+        // The player visits home and takes half of it
+        wm.switch_workspace(&home).unwrap();
+        assert!(wm.windows.lock().unwrap()[&editor].size.width < full.width);
+
+        // Leaving home lays it out without the player again
+        wm.switch_workspace(&other).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&editor].rect(), full);
+    }
+
+    #[test]
+    fn focus_moves_window_into_three_column_center() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Wide".to_string(), LayoutType::ThreeColumn);
+        wm.switch_workspace(&workspace_id).unwrap();
+        let first = wm.add_window("First".to_string(), "app".to_string()).unwrap();
+        let second = wm.add_window("Second".to_string(), "app".to_string()).unwrap();
+        let center = wm.windows.lock().unwrap()[&second].rect();
+
+        wm.focus_window(&first).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&first].rect(), center);
+    }
+
+    #[test]
+    fn resizing_tiles_below_the_minimum_is_an_error() {
+        let wm = window_manager();
+        {
+            let mut config = wm.config.lock().unwrap();
+            config.screen_width = MIN_TILE_SIZE;
+            config.screen_height = MIN_TILE_SIZE;
+        }
+        // No monitors, so the configured size stands in
+        *wm.monitors.lock().unwrap() = Some(Vec::new());
+
+        let left = wm.add_window("Left".to_string(), "app".to_string()).unwrap();
+        wm.add_window("Right".to_string(), "app".to_string()).unwrap();
+
+        assert!(wm.resize_tile(&left, Direction::Right, 10).is_err());
+    }
+
+    #[test]
+    fn resize_tile_uses_the_tiled_windows() {
+        let wm = window_manager();
+        let floating = wm.add_window("Dialog".to_string(), "app".to_string()).unwrap();
+        let left = wm.add_window("Left".to_string(), "app".to_string()).unwrap();
+        wm.add_window("Right".to_string(), "app".to_string()).unwrap();
+        wm.toggle_floating(&floating).unwrap();
+
+        assert!(wm.resize_tile(&floating, Direction::Right, 100).is_err());
+
+        let width = wm.windows.lock().unwrap()[&left].size.width;
+        wm.resize_tile(&left, Direction::Right, 100).unwrap();
+        assert!(wm.windows.lock().unwrap()[&left].size.width > width);
+    }
+
+    #[test]
+    fn bsp_splits_can_be_resized_rotated_and_preselected() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Bsp".to_string(), LayoutType::Bsp);
+        wm.switch_workspace(&workspace_id).unwrap();
+        let left = wm.add_window("Left".to_string(), "app".to_string()).unwrap();
+        let right = wm.add_window("Right".to_string(), "app".to_string()).unwrap();
+        let rect = |id: &str| wm.windows.lock().unwrap()[id].rect();
+        let (left_width, right_width) = (rect(&left).width, rect(&right).width);
+
+        wm.resize_bsp_split(&right, 0.1).unwrap();
+        assert!(rect(&right).width > right_width);
+        assert!(rect(&left).width < left_width);
+
+        // Side by side becomes stacked
+        wm.rotate_bsp_split(&left).unwrap();
+        assert_eq!(rect(&left).x, rect(&right).x);
+        assert!(rect(&left).y < rect(&right).y);
+
+        // The next window stacks under the focused one instead of beside it
+        wm.preselect_bsp_split(&workspace_id, Some(SplitDirection::Vertical)).unwrap();
+        let third = wm.add_window("Third".to_string(), "app".to_string()).unwrap();
+        assert_eq!(rect(&third).x, rect(&right).x);
+        assert!(rect(&third).y > rect(&right).y);
+        assert_eq!(wm.get_workspace(&workspace_id).unwrap().bsp_preselect, None);
+
+        let lone = wm.create_workspace("Lone".to_string(), LayoutType::Bsp);
+        let alone = wm.add_window("Alone".to_string(), "app".to_string()).unwrap();
+        wm.move_window_to_workspace(&alone, &lone, false).unwrap();
+        assert!(wm.rotate_bsp_split(&alone).is_err());
+        wm.workspaces.lock().unwrap().get_mut(&workspace_id).unwrap().set_layout(LayoutType::Tiling);
+        assert!(wm.resize_bsp_split(&left, 0.1).is_err());
+    }
+
+    #[test]
+    fn get_workspace_by_name_returns_created_id() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+
+        assert_eq!(wm.get_workspace_by_name("Code").map(|w| w.id), Some(workspace_id));
+        assert!(wm.get_workspace_by_name("Missing").is_none());
+    }
+
+    #[test]
+    fn workspace_numbers_are_one_based_positions() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+        let web = wm.create_workspace("Web".to_string(), LayoutType::Tiling);
+
+        assert_eq!(wm.switch_workspace_by_number(3), Ok(web.clone()));
+        assert_eq!(wm.get_active_workspace(), web);
+        assert_eq!(wm.switch_workspace_by_number(1), Ok(home.clone()));
+        assert_eq!(wm.get_active_workspace(), home);
+
+        assert!(wm.switch_workspace_by_number(0).is_err());
+        assert!(wm.switch_workspace_by_number(4).is_err());
+        assert_eq!(wm.get_active_workspace(), home);
+    }
+
+    #[test]
+    fn moving_a_workspace_reindexes_the_others() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let code = wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+        let web = wm.create_workspace("Web".to_string(), LayoutType::Tiling);
+        let order = |wm: &WindowManager| wm.get_workspaces().into_iter().map(|w| w.id).collect::<Vec<_>>();
+
+        wm.move_workspace(&web, 0).unwrap();
+        assert_eq!(order(&wm), vec![web.clone(), home.clone(), code.clone()]);
+
+        // Past the end clamps to the last position
+        wm.move_workspace(&web, 10).unwrap();
+        assert_eq!(order(&wm), vec![home.clone(), code.clone(), web.clone()]);
+        for (index, workspace) in wm.get_workspaces().iter().enumerate() {
+            assert_eq!(workspace.index, index);
+        }
+
+        assert!(wm.move_workspace("missing", 0).is_err());
+    }
+
+    #[test]
+    fn renamed_workspaces_are_found_by_their_new_name() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Code".to_string(), LayoutType::Tiling);
+
+        wm.rename_workspace(&workspace_id, "Editor".to_string()).unwrap();
+        assert_eq!(wm.get_workspace_by_name("Editor").map(|w| w.id), Some(workspace_id));
+        assert!(wm.get_workspace_by_name("Code").is_none());
+        assert!(wm.rename_workspace("missing", "Other".to_string()).is_err());
+    }
+
+    #[test]
+    fn migrating_a_workspace_keeps_the_targets_focus() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let kept = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        let scratch = wm.create_workspace("Scratch".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&scratch).unwrap();
+        let migrated = wm.add_window("Browser".to_string(), "browser".to_string()).unwrap();
+
+        wm.delete_workspace(&scratch, OrphanPolicy::Migrate(Some(home.clone()))).unwrap();
+        let workspace = wm.get_workspace(&home).unwrap();
+        assert_eq!(workspace.windows, vec![kept.clone(), migrated]);
+        assert_eq!(workspace.focused_window, Some(kept));
+        assert_eq!(wm.get_active_workspace(), home);
+    }
+
+    #[test]
+    fn deleting_the_active_workspace_runs_the_switch_hooks() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let scratch = wm.create_workspace("Scratch".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&scratch).unwrap();
+        wm.on_before_workspace_switch(|_: &mut String| HookResult::Veto("Pinned".to_string()));
+
+        assert_eq!(wm.delete_workspace(&scratch, OrphanPolicy::Migrate(None)), Err("Pinned".to_string()));
+        assert!(wm.get_workspace(&scratch).is_some());
+        assert_eq!(wm.get_active_workspace(), scratch);
+
+        let switched = Arc::new(Mutex::new(None));
+        *wm.hooks.lock().unwrap() = Hooks::default();
+        let seen = switched.clone();
+        wm.on_after_workspace_switch(move |event: &WorkspaceSwitched| {
+            *seen.lock().unwrap() = Some((event.workspace_id.clone(), event.previous.clone()));
+        });
+        wm.delete_workspace(&scratch, OrphanPolicy::Migrate(None)).unwrap();
+        assert_eq!(wm.get_active_workspace(), home);
+        assert_eq!(*switched.lock().unwrap(), Some((home, scratch)));
+    }
+
+    #[test]
+    fn closing_a_workspace_removes_its_windows_through_remove_window() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Scratch".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&workspace_id).unwrap();
+        let terminal_id = wm.add_window("Terminal".to_string(), "terminal".to_string()).unwrap();
+        let editor_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.swallow_terminal(&editor_id, &terminal_id).unwrap();
+
+        let vetoed = editor_id.clone();
+        wm.on_before_window_remove(move |id: &mut String| {
+            if *id == vetoed { HookResult::Veto("Unsaved changes".to_string()) } else { HookResult::Continue }
+        });
+        assert_eq!(wm.delete_workspace(&workspace_id, OrphanPolicy::Close), Err("Unsaved changes".to_string()));
+        assert!(wm.get_workspace(&workspace_id).is_some());
+
+        *wm.hooks.lock().unwrap() = Hooks::default();
+        wm.delete_workspace(&workspace_id, OrphanPolicy::Close).unwrap();
+        assert!(wm.get_workspace(&workspace_id).is_none());
+        // The terminal came back when the editor closed, and was closed too
+        assert!(wm.windows.lock().unwrap().is_empty());
+    }
+
+    #[test]
+    fn migrating_a_workspace_moves_swallowed_terminals() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Scratch".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&workspace_id).unwrap();
+        let terminal_id = wm.add_window("Terminal".to_string(), "terminal".to_string()).unwrap();
+        let editor_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.swallow_terminal(&editor_id, &terminal_id).unwrap();
+
+        wm.delete_workspace(&workspace_id, OrphanPolicy::Migrate(None)).unwrap();
+        let windows = wm.windows.lock().unwrap();
+        assert_eq!(windows[&terminal_id].workspace_id, windows[&editor_id].workspace_id);
+        assert_ne!(windows[&terminal_id].workspace_id, workspace_id);
+    }
+
+    #[test]
+    fn terminal_swallowed_by_a_scratchpad_window_returns_to_the_scratchpad() {
+        let wm = window_manager();
+        let terminal_id = wm.add_window("Terminal".to_string(), "terminal".to_string()).unwrap();
+        let editor_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.swallow_terminal(&editor_id, &terminal_id).unwrap();
+        wm.send_to_scratchpad(&editor_id).unwrap();
+
+        wm.remove_window(&editor_id).unwrap();
+        assert_eq!(wm.get_scratchpad().workspace.windows, vec![terminal_id.clone()]);
+        let terminal = wm.windows.lock().unwrap()[&terminal_id].clone();
+        assert_eq!(terminal.workspace_id, SCRATCHPAD_WORKSPACE_ID);
+        assert!(matches!(terminal.state, WindowState::Minimized));
+    }
+
+    #[cfg(target_os = "linux")]
+    #[test]
+    fn nearest_ancestor_terminal_swallows() {
+        let wm = window_manager();
+        let parent = parent_pid(std::process::id()).unwrap();
+        let Some(grandparent) = parent_pid(parent) else {
+            return;
+        };
+
+        let outer_id = wm.add_window("Outer".to_string(), "terminal".to_string()).unwrap();
+        let inner_id = wm.add_window("Inner".to_string(), "terminal".to_string()).unwrap();
+        for (id, pid) in [(&outer_id, grandparent), (&inner_id, parent)] {
+            let mut windows = wm.windows.lock().unwrap();
+            let window = windows.get_mut(id).unwrap();
+            window.terminal = true;
+            window.pid = Some(pid);
+        }
+
+        assert_eq!(wm.find_parent_terminal(std::process::id()), Some(inner_id));
+    }
+
+    #[test]
+    fn workspace_windows_stay_on_their_monitor() {
+        let wm = window_manager();
+        let monitor = |id: &str, x: i32, primary: bool| {
+            let geometry = Rect { x, y: 0, width: 1920, height: 1080 };
+            Monitor { id: id.to_string(), name: id.to_string(), geometry, work_area: geometry, primary, scale: 1.0 }
+        };
+        *wm.monitors.lock().unwrap() = Some(vec![monitor("DP-1", 0, true), monitor("DP-2", 1920, false)]);
+
+        let workspace_id = wm.create_workspace("Side".to_string(), LayoutType::Tiling);
+        wm.set_workspace_monitor(&workspace_id, Some("DP-2".to_string())).unwrap();
+        wm.reserve_strut(Some("DP-2".to_string()), Strut { top: 40, ..Default::default() }).unwrap();
+        wm.switch_workspace(&workspace_id).unwrap();
+        for title in ["One", "Two", "Three"] {
+            wm.add_window(title.to_string(), "app".to_string()).unwrap();
+        }
+        wm.arrange_workspace(&workspace_id).unwrap();
+
+        let area = Rect { x: 1920, y: 40, width: 1920, height: 1040 };
+        let windows = wm.get_workspace_windows(&workspace_id);
+        assert_eq!(windows.len(), 3);
+        for window in windows {
+            let (x, y) = (window.position.x, window.position.y);
+            assert!(x >= area.x && y >= area.y, "{:?} starts outside {:?}", window.position, area);
+            assert!(x + window.size.width as i32 <= area.x + area.width as i32, "{} overflows {:?}", window.title, area);
+            assert!(y + window.size.height as i32 <= area.y + area.height as i32, "{} overflows {:?}", window.title, area);
+        }
+    }
+
+    #[test]
+    fn refreshing_the_work_area_re_queries_monitors() {
+        let wm = window_manager();
+        let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.reserve_strut(None, Strut { top: 40, ..Default::default() }).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 10, y: 50, width: 1900, height: 1020 });
+
+        // A panel that has since quit still shrinks the cached monitor
+        let fallback = Monitor::from_config(&wm.config.lock().unwrap());
+        let with_panel = Monitor { work_area: Rect { y: 100, height: 980, ..fallback.geometry }, ..fallback };
+        *wm.monitors.lock().unwrap() = Some(vec![with_panel]);
+        wm.arrange_workspace(&wm.get_active_workspace()).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 10, y: 150, width: 1900, height: 920 });
+
+        wm.refresh_work_area().unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 10, y: 50, width: 1900, height: 1020 });
+        assert_eq!(*wm.monitors.lock().unwrap(), Some(Vec::new()));
+    }
+
+    /// Places only the first window, like a layout that hides the rest
+    struct FirstOnlyLayout;
+
+    impl Layout for FirstOnlyLayout {
+        fn arrange(&self, area: Rect, window_ids: &[String], _gap: u32, _workspace: &Workspace) -> Vec<(String, Rect)> {
+            window_ids.iter().take(1).map(|id| (id.clone(), area)).collect()
+        }
+    }
+
+    #[test]
+    fn resizing_a_tile_the_layout_left_out_is_an_error() {
+        let wm = window_manager();
+        wm.register_layout("tiling", Arc::new(FirstOnlyLayout));
+        let first_id = wm.add_window("One".to_string(), "app".to_string()).unwrap();
+        let second_id = wm.add_window("Two".to_string(), "app".to_string()).unwrap();
+
+        assert!(wm.resize_tile(&first_id, Direction::Right, 40).is_err());
+        assert!(wm.resize_tile(&second_id, Direction::Left, 40).is_err());
+    }
+
+    #[test]
+    fn sticky_window_can_be_reached_from_the_active_workspace() {
+        let wm = window_manager();
+        let sticky = wm.add_window("Player".to_string(), "player".to_string()).unwrap();
+        wm.toggle_sticky(&sticky).unwrap();
+
+        let other = wm.create_workspace("Other".to_string(), LayoutType::Tiling);
+        wm.switch_workspace(&other).unwrap();
+        let editor = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+
+        // The visiting window is tiled after the workspace's own, right of the editor
+        assert_eq!(wm.focus_direction(Direction::Right), Ok(sticky.clone()));
+        assert_eq!(wm.get_workspace(&other).unwrap().focused_window, Some(sticky.clone()));
+        assert_eq!(wm.focus_direction(Direction::Left), Ok(editor.clone()));
+        assert_eq!(wm.focus_next(), Ok(sticky.clone()));
+        assert_eq!(wm.focus_next(), Ok(editor.clone()));
+        assert!(wm.swap_direction(Direction::Right).is_err());
+
+        wm.focus_window(&sticky).unwrap();
+        wm.remove_window(&sticky).unwrap();
+        assert_eq!(wm.get_workspace(&other).unwrap().focused_window, Some(editor));
+    }
+
+    #[test]
+    fn directional_navigation_skips_floating_and_minimized_windows() {
+        let wm = window_manager();
+        let left = wm.add_window("Left".to_string(), "app".to_string()).unwrap();
+        let right = wm.add_window("Right".to_string(), "app".to_string()).unwrap();
+        let minimized = wm.add_window("Minimized".to_string(), "app".to_string()).unwrap();
+        let floating = wm.add_window("Floating".to_string(), "app".to_string()).unwrap();
+        wm.toggle_floating(&floating).unwrap();
+        wm.minimize_window(&minimized).unwrap();
+        {
+            let mut windows = wm.windows.lock().unwrap();
+            // Nearer than the right tile, which spans the other half
+            let window = windows.get_mut(&floating).unwrap();
+            window.position = WindowPosition { x: 980, y: 100 };
+            window.size = WindowSize { width: 20, height: 20 };
+        }
+
+        // The minimized window keeps its tile below the left one
+        wm.focus_window(&left).unwrap();
+        assert!(wm.focus_direction(Direction::Down).is_err());
+        assert_eq!(wm.focus_direction(Direction::Right), Ok(right.clone()));
+        assert_eq!(wm.focus_direction(Direction::Left), Ok(left.clone()));
+        assert!(wm.focus_direction(Direction::Left).is_err());
+
+        let workspace_id = wm.get_active_workspace();
+        wm.swap_direction(Direction::Right).unwrap();
+        let windows = wm.get_workspace(&workspace_id).unwrap().windows;
+        assert_eq!(windows[..2], [right.clone(), left.clone()]);
+        // Focus stays with the moved window, now on the right
+        assert_eq!(wm.focus_direction(Direction::Left), Ok(right));
+    }
+
+    #[test]
+    fn floating_windows_center_on_their_workspace() {
+        let wm = window_manager();
+        let window_id = wm.add_window("Dialog".to_string(), "dialog".to_string()).unwrap();
+        let position = |wm: &WindowManager| wm.windows.lock().unwrap()[&window_id].rect();
+
+        let tiled = position(&wm);
+        assert_eq!(wm.center_floating_window(&window_id), Err("Window is not floating".to_string()));
+        assert_eq!(position(&wm), tiled);
+
+        wm.toggle_floating(&window_id).unwrap();
+        wm.windows.lock().unwrap().get_mut(&window_id).unwrap().size = WindowSize { width: 800, height: 600 };
+        wm.center_floating_window(&window_id).unwrap();
+        assert_eq!(position(&wm), Rect { x: 560, y: 240, width: 800, height: 600 });
+
+        // Larger than the work area, so it overhangs every edge evenly
+        wm.windows.lock().unwrap().get_mut(&window_id).unwrap().size = WindowSize { width: 2400, height: 1200 };
+        wm.center_floating_window(&window_id).unwrap();
+        assert_eq!(position(&wm), Rect { x: -240, y: -60, width: 2400, height: 1200 });
+
+        assert!(wm.center_floating_window("missing").is_err());
+    }
+
+    #[test]
+    fn scratchpad_toggles_and_hides_once_emptied() {
+        let wm = window_manager();
+        let workspace_id = wm.get_active_workspace();
+        let notes = wm.add_window("Notes".to_string(), "notes".to_string()).unwrap();
+        assert_eq!(wm.toggle_scratchpad(), Err("Scratchpad is empty".to_string()));
+
+        wm.send_to_scratchpad(&notes).unwrap();
+        assert!(wm.get_workspace(&workspace_id).unwrap().windows.is_empty());
+        assert!(matches!(wm.windows.lock().unwrap()[&notes].state, WindowState::Minimized));
+
+        assert_eq!(wm.toggle_scratchpad(), Ok(true));
+        let shown = wm.windows.lock().unwrap()[&notes].clone();
+        assert!(matches!(shown.state, WindowState::Normal));
+        assert_eq!(shown.rect(), wm.scratchpad_rect());
+
+        // Taking the last window off a visible scratchpad hides it
+        wm.move_window_to_workspace(&notes, &workspace_id, false).unwrap();
+        assert!(!wm.get_scratchpad().visible);
+        assert_eq!(wm.get_workspace(&workspace_id).unwrap().windows, vec![notes.clone()]);
+
+        wm.send_to_scratchpad(&notes).unwrap();
+        assert!(matches!(wm.windows.lock().unwrap()[&notes].state, WindowState::Minimized));
+        assert_eq!(wm.toggle_scratchpad(), Ok(true));
+        assert_eq!(wm.toggle_scratchpad(), Ok(false));
+    }
+
+    /// Lines windows up in 100px wide columns from the left of the area
+    struct ColumnsLayout;
+
+    impl Layout for ColumnsLayout {
+        fn arrange(&self, area: Rect, window_ids: &[String], _gap: u32, _workspace: &Workspace) -> Vec<(String, Rect)> {
+            window_ids
+                .iter()
+                .enumerate()
+                .map(|(i, id)| (id.clone(), Rect { x: area.x + 100 * i as i32, width: 100, ..area }))
+                .collect()
+        }
+    }
+
+    #[test]
+    fn custom_layouts_arrange_workspaces() {
+        let wm = window_manager();
+        assert!(matches!(LayoutType::from_name("columns"), LayoutType::Custom(name) if name == "columns"));
+        let workspace_id = wm.create_workspace("Columns".to_string(), LayoutType::from_name("columns"));
+        wm.switch_workspace(&workspace_id).unwrap();
+        assert_eq!(
+            wm.arrange_workspace(&workspace_id),
+            Err("Layout 'columns' is not registered".to_string())
+        );
+
+        wm.register_layout("columns", Arc::new(ColumnsLayout));
+        assert!(wm.has_layout("columns"));
+        assert!(wm.get_layout_names().contains(&"columns".to_string()));
+
+        let first = wm.add_window("One".to_string(), "app".to_string()).unwrap();
+        let second = wm.add_window("Two".to_string(), "app".to_string()).unwrap();
+        let windows = wm.windows.lock().unwrap();
+        let area = wm.screen_area();
+        assert_eq!(windows[&first].rect(), Rect { width: 100, ..area });
+        assert_eq!(windows[&second].rect(), Rect { x: area.x + 100, width: 100, ..area });
+    }
+
+    #[test]
+    fn registered_layout_overrides_a_builtin() {
+        let wm = window_manager();
+        wm.register_layout(LayoutType::Monocle.name(), Arc::new(ColumnsLayout));
+        let workspace_id = wm.create_workspace("Monocle".to_string(), LayoutType::Monocle);
+        wm.switch_workspace(&workspace_id).unwrap();
+
+        wm.add_window("One".to_string(), "app".to_string()).unwrap();
+        let second = wm.add_window("Two".to_string(), "app".to_string()).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&second].position.x, wm.screen_area().x + 100);
+    }
+
+    #[test]
+    fn active_tab_follows_focus() {
+        let wm = window_manager();
+        let workspace_id = wm.create_workspace("Tabs".to_string(), LayoutType::Tabbed);
+        wm.switch_workspace(&workspace_id).unwrap();
+        let first = wm.add_window("One".to_string(), "app".to_string()).unwrap();
+        let second = wm.add_window("Two".to_string(), "app".to_string()).unwrap();
+        let floating = wm.add_window("Floating".to_string(), "app".to_string()).unwrap();
+        wm.toggle_floating(&floating).unwrap();
+
+        wm.focus_window(&first).unwrap();
+        let tabs = wm.get_tabs(&workspace_id).unwrap();
+        let summary: Vec<(&str, &str, usize, bool)> = tabs
+            .iter()
+            .map(|tab| (tab.window_id.as_str(), tab.title.as_str(), tab.index, tab.active))
+            .collect();
+        assert_eq!(summary, [(first.as_str(), "One", 0, true), (second.as_str(), "Two", 1, false)]);
+
+        wm.focus_next().unwrap();
+        let active: Vec<bool> = wm.get_tabs(&workspace_id).unwrap().iter().map(|tab| tab.active).collect();
+        assert_eq!(active, [false, true]);
+    }
+
+    #[test]
+    fn moving_windows_rearranges_both_workspaces() {
+        let wm = window_manager();
+        let source = wm.get_active_workspace();
+        let target = wm.create_workspace("Target".to_string(), LayoutType::Tiling);
+        let first = wm.add_window("One".to_string(), "app".to_string()).unwrap();
+        let second = wm.add_window("Two".to_string(), "app".to_string()).unwrap();
+        let half_width = wm.windows.lock().unwrap()[&first].size.width;
+
+        assert_eq!(
+            wm.move_window_to_workspace(&second, "missing", false),
+            Err("Workspace not found".to_string())
+        );
+
+        wm.move_window_to_workspace(&second, &target, false).unwrap();
+        assert_eq!(wm.get_active_workspace(), source);
+        assert_eq!(wm.get_workspace(&source).unwrap().windows, vec![first.clone()]);
+        assert_eq!(wm.get_workspace(&target).unwrap().windows, vec![second.clone()]);
+        {
+            // Both are alone now, so each fills its workspace
+            let windows = wm.windows.lock().unwrap();
+            assert_eq!(windows[&second].workspace_id, target);
+            assert!(windows[&first].size.width > half_width);
+            assert_eq!(windows[&first].rect(), windows[&second].rect());
+        }
+
+        wm.move_window_to_workspace(&first, &target, true).unwrap();
+        assert_eq!(wm.get_active_workspace(), target);
+        assert!(wm.get_workspace(&source).unwrap().windows.is_empty());
+        assert_eq!(wm.get_workspace(&target).unwrap().windows, vec![second, first]);
+    }
+
+    #[test]
+    fn vetoed_arrangements_do_not_fail_the_change() {
+        let wm = window_manager();
+        let workspace_id = wm.get_active_workspace();
+        let added = Arc::new(Mutex::new(Vec::new()));
+        wm.on_before_arrange(|_: &mut Workspace| HookResult::Veto("Frozen".to_string()));
+        let seen = added.clone();
+        wm.on_after_window_add(move |window: &ManagedWindow| seen.lock().unwrap().push(window.id.clone()));
+
+        let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        assert_eq!(*added.lock().unwrap(), vec![window_id.clone()]);
+        assert_eq!(wm.get_workspace(&workspace_id).unwrap().windows, vec![window_id.clone()]);
+        // Never arranged, so it keeps the size it was created with
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 0, y: 0, width: 800, height: 600 });
+
+        wm.remove_window(&window_id).unwrap();
+        assert!(wm.windows.lock().unwrap().is_empty());
+        assert!(wm.get_workspace(&workspace_id).unwrap().windows.is_empty());
+    }
+
+    #[test]
+    fn before_hooks_can_veto_and_redirect() {
+        let wm = window_manager();
+        let home = wm.get_active_workspace();
+        let dev = wm.create_workspace("Dev".to_string(), LayoutType::Tiling);
+        wm.on_before_window_add(|window: &mut WindowAdd| match window.app_name.as_str() {
+            "spam" => HookResult::Veto("Blocked".to_string()),
+            "terminal" => {
+                window.workspace_id = "Dev".to_string();
+                HookResult::Continue
+            }
+            _ => HookResult::Continue,
+        });
+
+        assert_eq!(wm.add_window("Ad".to_string(), "spam".to_string()), Err("Blocked".to_string()));
+        assert!(wm.windows.lock().unwrap().is_empty());
+
+        let terminal = wm.add_window("Shell".to_string(), "terminal".to_string()).unwrap();
+        assert_eq!(wm.windows.lock().unwrap()[&terminal].workspace_id, dev);
+        assert_eq!(wm.get_workspace(&dev).unwrap().windows, vec![terminal]);
+
+        let first = wm.add_window("One".to_string(), "editor".to_string()).unwrap();
+        let second = wm.add_window("Two".to_string(), "editor".to_string()).unwrap();
+        let redirected = second.clone();
+        wm.on_before_focus(move |id: &mut String| {
+            *id = redirected.clone();
+            HookResult::Continue
+        });
+        let focused = Arc::new(Mutex::new(None));
+        let seen = focused.clone();
+        wm.on_after_focus(move |event: &WindowFocused| {
+            *seen.lock().unwrap() = Some(event.window_id.clone());
+        });
+
+        wm.focus_window(&first).unwrap();
+        assert_eq!(wm.get_workspace(&home).unwrap().focused_window, Some(second.clone()));
+        assert_eq!(*focused.lock().unwrap(), Some(second));
+    }
+
+    #[test]
+    fn hooks_can_register_other_hooks() {
+        let wm = Arc::new(window_manager());
+        let focused = Arc::new(Mutex::new(Vec::new()));
+        let (inner, seen) = (wm.clone(), focused.clone());
+        wm.on_after_window_add(move |_: &ManagedWindow| {
+            let seen = seen.clone();
+            inner.on_after_focus(move |event: &WindowFocused| seen.lock().unwrap().push(event.window_id.clone()));
+        });
+
+        let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+        wm.focus_window(&window_id).unwrap();
+        assert!(focused.lock().unwrap().contains(&window_id));
+    }
+}
@@ -1,14 +1,42 @@
+use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
-use crate::layout::LayoutType;
+use crate::layout::{LayoutType, TileWeight};
+use crate::bsp::{BspNode, SplitDirection};
+
+pub const DEFAULT_MASTER_RATIO: f32 = 0.55;
+pub const MIN_MASTER_RATIO: f32 = 0.1;
+pub const MAX_MASTER_RATIO: f32 = 0.9;
+
+/// What happens to the windows of a deleted workspace
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub enum OrphanPolicy {
+    /// Move them to the given workspace, or to the first remaining one
+    Migrate(Option<String>),
+    /// Close them along with the workspace
+    Close,
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Workspace {
     pub id: String,
     pub name: String,
+    /// Zero-based position in the workspace list; workspace number N has index N - 1
+    pub index: usize,
     pub layout: LayoutType,
     pub windows: Vec<String>,
     pub focused_window: Option<String>,
+    pub master_ratio: f32,
+    pub master_count: usize,
+    pub bsp_tree: Option<BspNode>,
+    /// Direction the next window splits its leaf in, used once; `None`
+    /// keeps alternating with depth
+    pub bsp_preselect: Option<SplitDirection>,
+    /// Free-form settings read by custom layouts
+    pub layout_options: HashMap<String, serde_json::Value>,
+    pub tile_weights: HashMap<String, TileWeight>,
+    /// Monitor the workspace is shown on; `None` means the primary monitor
+    pub monitor: Option<String>,
 }
 
 impl Workspace {
@@ -16,21 +44,57 @@ impl Workspace {
         Self {
             id: Uuid::new_v4().to_string(),
             name,
+            index: 0,
             layout,
             windows: Vec::new(),
             focused_window: None,
+            master_ratio: DEFAULT_MASTER_RATIO,
+            master_count: 1,
+            bsp_tree: None,
+            bsp_preselect: None,
+            layout_options: HashMap::new(),
+            tile_weights: HashMap::new(),
+            monitor: None,
         }
     }
 
     pub fn add_window(&mut self, window_id: String) {
         if !self.windows.contains(&window_id) {
-            self.windows.push(window_id.clone());
+            self.add_window_unfocused(window_id.clone());
             self.focused_window = Some(window_id);
         }
     }
 
+    /// Adds a window without taking focus, e.g. a sticky window joining the
+    /// active workspace's layout
+    pub fn add_window_unfocused(&mut self, window_id: String) {
+        if !self.windows.contains(&window_id) {
+            self.insert_into_bsp_tree(window_id.clone());
+            self.windows.push(window_id);
+        }
+    }
+
+    fn insert_into_bsp_tree(&mut self, window_id: String) {
+        let target = self.focused_window.clone().or_else(|| self.windows.last().cloned());
+
+        match (&mut self.bsp_tree, target) {
+            (Some(tree), Some(target)) if tree.contains(&target) => {
+                tree.split_leaf_in(&target, window_id, self.bsp_preselect.take());
+            }
+            (Some(tree), _) => {
+                // Fall back to splitting whichever leaf was added last
+                if let Some(last) = tree.window_ids().pop() {
+                    tree.split_leaf_in(&last, window_id, self.bsp_preselect.take());
+                }
+            }
+            (None, _) => self.bsp_tree = Some(BspNode::leaf(window_id)),
+        }
+    }
+
     pub fn remove_window(&mut self, window_id: &str) {
         self.windows.retain(|id| id != window_id);
+        self.bsp_tree = self.bsp_tree.take().and_then(|tree| tree.remove(window_id));
+        self.tile_weights.remove(window_id);
         
         if self.focused_window.as_ref() == Some(&window_id.to_string()) {
             self.focused_window = self.windows.last().cloned();
@@ -43,6 +107,68 @@ impl Workspace {
         }
     }
 
+    /// Moves focus to the next window in order, wrapping around at the end
+    pub fn focus_next(&mut self) -> Option<String> {
+        self.cycle_focus(1)
+    }
+
+    /// Moves focus to the previous window in order, wrapping around at the start
+    pub fn focus_prev(&mut self) -> Option<String> {
+        self.cycle_focus(-1)
+    }
+
+    fn cycle_focus(&mut self, step: isize) -> Option<String> {
+        if self.windows.is_empty() {
+            return None;
+        }
+
+        let current = self.focused_window
+            .as_ref()
+            .and_then(|focused| self.windows.iter().position(|id| id == focused));
+        let next = match current {
+            Some(i) => (i as isize + step).rem_euclid(self.windows.len() as isize) as usize,
+            None if step > 0 => 0,
+            None => self.windows.len() - 1,
+        };
+
+        self.focused_window = Some(self.windows[next].clone());
+        self.focused_window.clone()
+    }
+
+    /// Exchanges the places of two windows in the layout order and BSP tree
+    pub fn swap_windows(&mut self, a: &str, b: &str) {
+        let a_index = self.windows.iter().position(|id| id == a);
+        let b_index = self.windows.iter().position(|id| id == b);
+
+        if let (Some(a_index), Some(b_index)) = (a_index, b_index) {
+            self.windows.swap(a_index, b_index);
+            if let Some(tree) = &mut self.bsp_tree {
+                tree.swap_windows(a, b);
+            }
+        }
+    }
+
+    /// Puts `new` in the slot held by `old`, keeping its position in the
+    /// window order, its BSP leaf, tile weight and focus
+    pub fn replace_window(&mut self, old: &str, new: String) {
+        if self.windows.contains(&new) {
+            return;
+        }
+        if let Some(index) = self.windows.iter().position(|id| id == old) {
+            self.windows[index] = new.clone();
+            if let Some(tree) = &mut self.bsp_tree {
+                // `new` is not in the tree, so swapping just renames the leaf
+                tree.swap_windows(old, &new);
+            }
+            if let Some(weight) = self.tile_weights.remove(old) {
+                self.tile_weights.insert(new.clone(), weight);
+            }
+            if self.focused_window.as_deref() == Some(old) {
+                self.focused_window = Some(new);
+            }
+        }
+    }
+
     pub fn get_windows(&self) -> &[String] {
         &self.windows
     }
@@ -50,4 +176,22 @@ impl Workspace {
     pub fn set_layout(&mut self, layout: LayoutType) {
         self.layout = layout;
     }
+
+    pub fn adjust_master_ratio(&mut self, delta: f32) {
+        self.master_ratio = (self.master_ratio + delta).clamp(MIN_MASTER_RATIO, MAX_MASTER_RATIO);
+    }
+
+    pub fn adjust_master_count(&mut self, delta: i32) {
+        self.master_count = self.master_count.saturating_add_signed(delta as isize);
+    }
+
+    pub fn tile_weight(&self, window_id: &str) -> TileWeight {
+        self.tile_weights.get(window_id).copied().unwrap_or_default()
+    }
+
+    pub fn scale_tile_weight(&mut self, window_id: &str, width_factor: f32, height_factor: f32) {
+        let weight = self.tile_weights.entry(window_id.to_string()).or_default();
+        weight.width *= width_factor;
+        weight.height *= height_factor;
+    }
 }
\ No newline at end of file
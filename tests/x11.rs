@@ -0,0 +1,715 @@
+//! Tests of the X11 backend against a private Xvfb server. They pass without
+//! doing anything when `Xvfb` is not installed.
+#![cfg(target_os = "linux")]
+
+use std::ffi::CString;
+use std::io::{BufRead, BufReader};
+use std::os::raw::{c_int, c_ulong};
+use std::os::unix::process::CommandExt;
+use std::process::{Child, Command, Stdio};
+use std::ptr;
+use std::sync::atomic::{AtomicPtr, AtomicUsize, Ordering};
+use std::sync::{mpsc, Mutex, MutexGuard, OnceLock};
+use std::thread;
+use std::time::{Duration, Instant};
+use tauri_winutils::system_window::parent_pid;
+use tauri_winutils::{
+    PlatformWindowManager, Rect, RuleActions, SystemWindow, SystemWindowEvent, SystemWindowManager, WindowManager, WindowRule,
+    WindowStateFlag, X11Backend,
+};
+use x11::xlib::*;
+
+/// Display name of the test server, started on first use; `None` without Xvfb
+static SERVER: OnceLock<Option<String>> = OnceLock::new();
+
+/// Tests swap the process-wide error handler, so they run one at a time
+static SERIAL: Mutex<()> = Mutex::new(());
+
+/// Starts the server if needed and holds it for one test, or returns `None`
+/// when it cannot run
+fn server() -> Option<MutexGuard<'static, ()>> {
+    let guard = SERIAL.lock().unwrap_or_else(|e| e.into_inner());
+    if SERVER.get_or_init(start_server).is_none() {
+        eprintln!("Xvfb is not installed, skipping");
+        return None;
+    }
+    Some(guard)
+}
+
+fn start_server() -> Option<String> {
+    // Children are killed when the thread that spawned them exits, so they
+    // are spawned from one that lives as long as the test process
+    let (sender, receiver) = mpsc::channel();
+    thread::spawn(move || {
+        let mut xvfb = Command::new("Xvfb");
+        xvfb.args(["-displayfd", "1", "-nolisten", "tcp", "-screen", "0", "1280x1024x24"])
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null());
+        let mut child = spawn_with_parent(&mut xvfb).ok();
+        let _ = sender.send(child.as_mut().and_then(read_display_number));
+        loop {
+            thread::park();
+        }
+    });
+
+    let display = receiver.recv().ok().flatten()?;
+    // The backend connects to $DISPLAY, and nothing has used it yet
+    std::env::set_var("DISPLAY", &display);
+    Some(display)
+}
+
+/// Xvfb writes the display number to `-displayfd` once it accepts connections
+fn read_display_number(xvfb: &mut Child) -> Option<String> {
+    let mut line = String::new();
+    BufReader::new(xvfb.stdout.as_mut()?).read_line(&mut line).ok()?;
+    let number = line.trim();
+    (!number.is_empty()).then(|| format!(":{}", number))
+}
+
+fn spawn_with_parent(command: &mut Command) -> std::io::Result<Child> {
+    unsafe {
+        command.pre_exec(|| {
+            libc::prctl(libc::PR_SET_PDEATHSIG, libc::SIGTERM);
+            Ok(())
+        });
+    }
+    command.spawn()
+}
+
+/// Sets `_NET_WM_PID`, which the backend reads a window's process from
+unsafe fn set_pid(display: *mut Display, window: Window, pid: u32) {
+    let hostname = std::fs::read_to_string("/proc/sys/kernel/hostname").unwrap();
+    set_pid_on(display, window, pid, hostname.trim_end());
+}
+
+/// Labels `window` with `pid` on the host named `machine`
+unsafe fn set_pid_on(display: *mut Display, window: Window, pid: u32, machine: &str) {
+    let name = CString::new("_NET_WM_PID").unwrap();
+    let property = XInternAtom(display, name.as_ptr(), False);
+    let pid = pid as c_ulong;
+    XChangeProperty(
+        display, window, property, XA_CARDINAL, 32, PropModeReplace,
+        &pid as *const c_ulong as *const u8, 1,
+    );
+    XChangeProperty(
+        display, window, XA_WM_CLIENT_MACHINE, XA_STRING, 8, PropModeReplace,
+        machine.as_ptr(), machine.len() as c_int,
+    );
+    XSync(display, False);
+}
+
+/// Map state and root-relative geometry as the server has them
+unsafe fn window_geometry(display: *mut Display, window: Window) -> (c_int, (i32, i32, i32, i32)) {
+    let mut attributes: XWindowAttributes = std::mem::zeroed();
+    XGetWindowAttributes(display, window, &mut attributes);
+    (attributes.map_state, (attributes.x, attributes.y, attributes.width, attributes.height))
+}
+
+/// Window `window` is nested in, which a reparenting window manager makes its frame
+unsafe fn parent_window(display: *mut Display, window: Window) -> Window {
+    let mut root = 0;
+    let mut parent = 0;
+    let mut children = ptr::null_mut();
+    let mut count = 0;
+    XQueryTree(display, window, &mut root, &mut parent, &mut children, &mut count);
+    if !children.is_null() {
+        XFree(children as *mut _);
+    }
+    parent
+}
+
+/// Handles `get_all_windows` lists
+fn listed_handles() -> Vec<u64> {
+    PlatformWindowManager::get_all_windows().unwrap().iter().map(|w| w.handle).collect()
+}
+
+/// Window manager started for one test and killed when dropped
+struct WindowManagerProcess(Child);
+
+impl Drop for WindowManagerProcess {
+    fn drop(&mut self) {
+        let _ = self.0.kill();
+        let _ = self.0.wait();
+    }
+}
+
+/// Starts the EWMH window manager named by `X11_TEST_WM` (openbox by
+/// default) and waits until it has announced itself, or returns `None` when
+/// it is not installed
+fn start_window_manager(display: *mut Display) -> Option<WindowManagerProcess> {
+    let command = std::env::var("X11_TEST_WM").unwrap_or_else(|_| "openbox".to_string());
+    let mut args = command.split_whitespace();
+    let mut wm = Command::new(args.next()?);
+    wm.args(args).stdout(Stdio::null()).stderr(Stdio::null());
+    let Ok(child) = spawn_with_parent(&mut wm) else {
+        eprintln!("{} is not installed, skipping", command);
+        return None;
+    };
+    let wm = WindowManagerProcess(child);
+
+    let ready = wait_until(|| unsafe {
+        let root = XDefaultRootWindow(display);
+        let check = window_property(display, root, "_NET_SUPPORTING_WM_CHECK");
+        check.first().is_some_and(|&child| window_property(display, child, "_NET_SUPPORTING_WM_CHECK") == check)
+    });
+    assert!(ready, "{} did not start", command);
+    Some(wm)
+}
+
+/// Splits the screen into two 640px wide RandR monitors until dropped
+struct SplitMonitors;
+
+impl SplitMonitors {
+    /// Returns `None` when xrandr is not installed
+    fn new() -> Option<Self> {
+        let xrandr = |args: &[&str]| Command::new("xrandr").args(args).stderr(Stdio::null()).status();
+        let Ok(status) = xrandr(&["--setmonitor", "left", "640/169x1024/270+0+0", "screen"]) else {
+            eprintln!("xrandr is not installed, skipping");
+            return None;
+        };
+        let split = SplitMonitors;
+        assert!(status.success(), "xrandr could not add the left monitor");
+        let status = xrandr(&["--setmonitor", "right", "640/169x1024/270+640+0", "none"]).unwrap();
+        assert!(status.success(), "xrandr could not add the right monitor");
+        Some(split)
+    }
+}
+
+impl Drop for SplitMonitors {
+    fn drop(&mut self) {
+        for name in ["left", "right"] {
+            let _ = Command::new("xrandr").args(["--delmonitor", name]).stderr(Stdio::null()).status();
+        }
+    }
+}
+
+/// Polls `condition` for up to five seconds
+fn wait_until(mut condition: impl FnMut() -> bool) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(5);
+    while Instant::now() < deadline {
+        if condition() {
+            return true;
+        }
+        thread::sleep(Duration::from_millis(20));
+    }
+    false
+}
+
+/// Values of a format 32 property, empty when the window does not have it
+unsafe fn window_property(display: *mut Display, window: Window, name: &str) -> Vec<c_ulong> {
+    let name = CString::new(name).unwrap();
+    let property = XInternAtom(display, name.as_ptr(), False);
+    let mut actual_type = 0;
+    let mut actual_format = 0;
+    let mut nitems = 0;
+    let mut bytes_after = 0;
+    let mut data: *mut u8 = ptr::null_mut();
+
+    XGetWindowProperty(
+        display, window, property, 0, 1024, False, AnyPropertyType as Atom,
+        &mut actual_type, &mut actual_format, &mut nitems, &mut bytes_after, &mut data,
+    );
+    if data.is_null() {
+        return Vec::new();
+    }
+    let values = if actual_format == 32 {
+        std::slice::from_raw_parts(data as *const c_ulong, nitems as usize).to_vec()
+    } else {
+        Vec::new()
+    };
+    XFree(data as *mut _);
+    values
+}
+
+/// Opens a client connection of the test's own to the test server
+fn open_display() -> *mut Display {
+    let display = unsafe { XOpenDisplay(ptr::null()) };
+    assert!(!display.is_null(), "Cannot connect to the test server");
+    display
+}
+
+/// Creates and maps a titled top-level window
+unsafe fn create_window(display: *mut Display, title: &str) -> Window {
+    let root = XDefaultRootWindow(display);
+    let window = XCreateSimpleWindow(display, root, 0, 0, 200, 100, 0, 0, 0);
+    let title = CString::new(title).unwrap();
+    XStoreName(display, window, title.as_ptr());
+    XMapWindow(display, window);
+    XSync(display, False);
+    window
+}
+
+#[test]
+fn destroyed_window_is_an_error() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let live = create_window(display, "live");
+        let destroyed = create_window(display, "destroyed");
+        XDestroyWindow(display, destroyed);
+        XSync(display, False);
+
+        let error = PlatformWindowManager::move_window(destroyed as u64, 10, 10).unwrap_err();
+        assert!(error.contains("BadWindow"), "unexpected error: {}", error);
+        let error = PlatformWindowManager::resize_window(destroyed as u64, 300, 200).unwrap_err();
+        assert!(error.contains("BadWindow"), "unexpected error: {}", error);
+        assert!(PlatformWindowManager::get_window_by_handle(destroyed as u64).unwrap().is_none());
+
+        // The error was reported once, and the backend keeps working
+        PlatformWindowManager::move_window(live as u64, 10, 10).unwrap();
+        assert!(PlatformWindowManager::get_window_by_handle(live as u64).unwrap().is_some());
+
+        XCloseDisplay(display);
+    }
+}
+
+/// Connection whose errors `count_error` counts
+static OTHER_DISPLAY: AtomicPtr<Display> = AtomicPtr::new(ptr::null_mut());
+static OTHER_ERRORS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe extern "C" fn count_error(display: *mut Display, _event: *mut XErrorEvent) -> c_int {
+    if display == OTHER_DISPLAY.load(Ordering::SeqCst) {
+        OTHER_ERRORS.fetch_add(1, Ordering::SeqCst);
+    }
+    0
+}
+
+#[test]
+fn errors_on_other_connections_reach_their_handler() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        OTHER_DISPLAY.store(display, Ordering::SeqCst);
+
+        // Like GDK, install a handler of our own, possibly after the backend's
+        XSetErrorHandler(Some(count_error));
+        PlatformWindowManager::get_all_windows().unwrap();
+
+        let window = create_window(display, "other");
+        XDestroyWindow(display, window);
+        XMapWindow(display, window);
+        XSync(display, False);
+        assert_eq!(OTHER_ERRORS.load(Ordering::SeqCst), 1);
+
+        // The backend still gets its own errors
+        let error = PlatformWindowManager::move_window(window as u64, 10, 10).unwrap_err();
+        assert!(error.contains("BadWindow"), "unexpected error: {}", error);
+        assert_eq!(OTHER_ERRORS.load(Ordering::SeqCst), 1);
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn title_changes_arrive_without_a_pending_request() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let window = create_window(display, "before");
+        let events = X11Backend::global().unwrap().subscribe().unwrap();
+
+        // Only the server can wake the backend thread up for this
+        let net_wm_name = XInternAtom(display, CString::new("_NET_WM_NAME").unwrap().as_ptr(), False);
+        let utf8_string = XInternAtom(display, CString::new("UTF8_STRING").unwrap().as_ptr(), False);
+        let title = "après";
+        XChangeProperty(
+            display, window, net_wm_name, utf8_string, 8, PropModeReplace,
+            title.as_ptr(), title.len() as c_int,
+        );
+        XSync(display, False);
+
+        let changed = std::iter::from_fn(|| events.recv_timeout(Duration::from_secs(5)).ok())
+            .find_map(|event| match event {
+                SystemWindowEvent::TitleChanged { handle, title } if handle == window as u64 => Some(title),
+                _ => None,
+            });
+        assert_eq!(changed.as_deref(), Some(title));
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn window_states_round_trip_through_the_window_manager() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let Some(_wm) = start_window_manager(display) else {
+            XCloseDisplay(display);
+            return;
+        };
+
+        // State requests are ignored for windows the manager has not adopted yet
+        let window = create_window(display, "states");
+        let root = XDefaultRootWindow(display);
+        assert!(wait_until(|| window_property(display, root, "_NET_CLIENT_LIST").contains(&window)));
+
+        let handle = window as u64;
+        let state = |check: fn(&SystemWindow) -> bool| {
+            wait_until(|| {
+                PlatformWindowManager::get_window_by_handle(handle)
+                    .unwrap()
+                    .is_some_and(|window| check(&window))
+            })
+        };
+
+        PlatformWindowManager::maximize_window(handle).unwrap();
+        assert!(state(|w| w.is_maximized), "window was not maximized");
+        PlatformWindowManager::restore_window(handle).unwrap();
+        assert!(state(|w| !w.is_maximized), "window stayed maximized");
+
+        PlatformWindowManager::set_window_state(handle, WindowStateFlag::Maximized, true).unwrap();
+        assert!(state(|w| w.is_maximized), "window was not maximized");
+        PlatformWindowManager::set_window_state(handle, WindowStateFlag::Maximized, false).unwrap();
+        assert!(state(|w| !w.is_maximized), "window stayed maximized");
+
+        PlatformWindowManager::set_window_state(handle, WindowStateFlag::Fullscreen, true).unwrap();
+        assert!(state(|w| w.is_fullscreen), "window did not become fullscreen");
+        PlatformWindowManager::restore_window(handle).unwrap();
+        assert!(state(|w| !w.is_fullscreen), "window stayed fullscreen");
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn clients_are_listed_instead_of_their_frames() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let Some(_wm) = start_window_manager(display) else {
+            XCloseDisplay(display);
+            return;
+        };
+
+        let client = create_window(display, "framed");
+        let root = XDefaultRootWindow(display);
+        assert!(wait_until(|| window_property(display, root, "_NET_CLIENT_LIST").contains(&client)));
+        assert!(wait_until(|| parent_window(display, client) != root), "the client was not reparented");
+        let frame = parent_window(display, client);
+
+        let handles = listed_handles();
+        assert!(handles.contains(&(client as u64)), "{:x} missing from {:x?}", client, handles);
+        assert!(!handles.contains(&(frame as u64)));
+
+        // Without the client lists, clients are found below the frames by WM_STATE
+        for name in ["_NET_CLIENT_LIST_STACKING", "_NET_CLIENT_LIST"] {
+            let name = CString::new(name).unwrap();
+            XDeleteProperty(display, root, XInternAtom(display, name.as_ptr(), False));
+        }
+        XSync(display, False);
+        let handles = listed_handles();
+        assert!(handles.contains(&(client as u64)), "{:x} missing from {:x?}", client, handles);
+        assert!(!handles.contains(&(frame as u64)));
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn top_level_windows_are_clients_without_a_window_manager() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        // Nothing sets WM_STATE, so the window is its own client
+        let window = create_window(display, "unmanaged");
+        let handles = listed_handles();
+        assert!(handles.contains(&(window as u64)), "{:x} missing from {:x?}", window, handles);
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn windows_report_their_process() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let window = create_window(display, "process");
+        let name = CString::new("winutils-test").unwrap();
+        let class = CString::new("WinutilsTest").unwrap();
+        let mut hint = XClassHint { res_name: name.as_ptr() as *mut _, res_class: class.as_ptr() as *mut _ };
+        XSetClassHint(display, window, &mut hint);
+        set_pid(display, window, std::process::id());
+
+        let info = PlatformWindowManager::get_window_by_handle(window as u64).unwrap().unwrap();
+        let exe = std::env::current_exe().unwrap();
+        assert_eq!(info.pid, std::process::id());
+        assert_eq!(info.process_name, exe.file_name().unwrap().to_string_lossy());
+        assert_eq!(info.class, "WinutilsTest");
+        assert_eq!(info.command_line, std::env::args().collect::<Vec<_>>());
+
+        // Without _NET_WM_PID the server is asked which client owns the
+        // window, when libXRes is installed
+        let unlabelled = create_window(display, "unlabelled");
+        let info = PlatformWindowManager::get_window_by_handle(unlabelled as u64).unwrap().unwrap();
+        if info.pid != 0 {
+            assert_eq!(info.pid, std::process::id());
+            assert_eq!(info.process_name, exe.file_name().unwrap().to_string_lossy());
+        }
+
+        // A pid from another host says nothing about local processes
+        let remote = create_window(display, "remote");
+        let local_pid = parent_pid(std::process::id()).unwrap();
+        set_pid_on(display, remote, local_pid, "elsewhere.invalid");
+        let info = PlatformWindowManager::get_window_by_handle(remote as u64).unwrap().unwrap();
+        assert_ne!(info.pid, local_pid);
+        if info.pid == 0 {
+            assert_eq!(info.process_name, "Unknown");
+            assert!(info.command_line.is_empty());
+        }
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn primary_monitor_matches_the_screen() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    let monitors = PlatformWindowManager::get_monitors().unwrap();
+    let primary = monitors.iter().find(|m| m.primary).expect("no primary monitor");
+    assert_eq!((primary.geometry.x, primary.geometry.y), (0, 0));
+    assert_eq!((primary.geometry.width, primary.geometry.height), (1280, 1024));
+
+    unsafe {
+        // A 24px panel along the top edge
+        let display = open_display();
+        let panel = create_window(display, "panel");
+        let strut = CString::new("_NET_WM_STRUT_PARTIAL").unwrap();
+        let strut = XInternAtom(display, strut.as_ptr(), False);
+        let values: [c_ulong; 12] = [0, 0, 24, 0, 0, 0, 0, 0, 0, 1279, 0, 0];
+        XChangeProperty(
+            display, panel, strut, XA_CARDINAL, 32, PropModeReplace,
+            values.as_ptr() as *const u8, values.len() as c_int,
+        );
+        XSync(display, False);
+
+        let monitors = PlatformWindowManager::get_monitors().unwrap();
+        let primary = monitors.iter().find(|m| m.primary).unwrap();
+        assert_eq!(primary.work_area.y, 24);
+        assert_eq!((primary.work_area.width, primary.work_area.height), (1280, 1000));
+
+        // A hidden panel reserves nothing
+        XUnmapWindow(display, panel);
+        XSync(display, False);
+        let monitors = PlatformWindowManager::get_monitors().unwrap();
+        let primary = monitors.iter().find(|m| m.primary).unwrap();
+        assert_eq!(primary.work_area, primary.geometry);
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn refreshing_the_work_area_re_reads_panels() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    let wm = WindowManager::new();
+    let window_id = wm.add_window("Editor".to_string(), "editor".to_string()).unwrap();
+    let screen = Rect { x: 0, y: 0, width: 1280, height: 1024 };
+    // Tiled with the default 10px gap
+    assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 10, y: 10, width: 1260, height: 1004 });
+
+    unsafe {
+        // A 24px panel along the top edge starts after the monitors were read
+        let display = open_display();
+        let panel = create_window(display, "panel");
+        let strut = CString::new("_NET_WM_STRUT_PARTIAL").unwrap();
+        let strut = XInternAtom(display, strut.as_ptr(), False);
+        let values: [c_ulong; 12] = [0, 0, 24, 0, 0, 0, 0, 0, 0, 1279, 0, 0];
+        XChangeProperty(
+            display, panel, strut, XA_CARDINAL, 32, PropModeReplace,
+            values.as_ptr() as *const u8, values.len() as c_int,
+        );
+        XSync(display, False);
+
+        wm.refresh_work_area().unwrap();
+        let monitors = wm.monitors.lock().unwrap().clone().unwrap();
+        let primary = monitors.iter().find(|m| m.primary).unwrap();
+        assert_eq!(primary.geometry, screen);
+        assert_eq!(primary.work_area, Rect { y: 24, height: 1000, ..screen });
+
+        assert_eq!(wm.windows.lock().unwrap()[&window_id].rect(), Rect { x: 10, y: 34, width: 1260, height: 980 });
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn struts_only_shrink_the_monitor_they_are_on() {
+    let Some(_server) = server() else {
+        return;
+    };
+    let Some(_split) = SplitMonitors::new() else {
+        return;
+    };
+
+    let monitors = PlatformWindowManager::get_monitors().unwrap();
+    let monitor = |name: &str| monitors.iter().find(|m| m.name == name).cloned().expect("missing monitor");
+    assert_eq!(monitors.len(), 2);
+    assert_eq!((monitor("left").geometry.x, monitor("left").geometry.width), (0, 640));
+    assert_eq!((monitor("right").geometry.x, monitor("right").geometry.width), (640, 640));
+
+    unsafe {
+        // A 24px panel along the top of the left monitor and a 30px one along
+        // the bottom of the right monitor
+        let display = open_display();
+        let strut = CString::new("_NET_WM_STRUT_PARTIAL").unwrap();
+        let strut = XInternAtom(display, strut.as_ptr(), False);
+        let panels: [[c_ulong; 12]; 2] = [
+            [0, 0, 24, 0, 0, 0, 0, 0, 0, 639, 0, 0],
+            [0, 0, 0, 30, 0, 0, 0, 0, 0, 0, 640, 1279],
+        ];
+        for values in &panels {
+            let panel = create_window(display, "panel");
+            XChangeProperty(
+                display, panel, strut, XA_CARDINAL, 32, PropModeReplace,
+                values.as_ptr() as *const u8, values.len() as c_int,
+            );
+        }
+        XSync(display, False);
+
+        let monitors = PlatformWindowManager::get_monitors().unwrap();
+        let work_area = |name: &str| monitors.iter().find(|m| m.name == name).unwrap().work_area;
+        let left = work_area("left");
+        assert_eq!((left.x, left.y, left.width, left.height), (0, 24, 640, 1000));
+        let right = work_area("right");
+        assert_eq!((right.x, right.y, right.width, right.height), (640, 0, 640, 994));
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn swallowing_moves_and_hides_native_windows() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        // The test process stands in for a program launched from a terminal
+        // run by its parent
+        let terminal = create_window(display, "terminal");
+        set_pid(display, terminal, parent_pid(std::process::id()).unwrap());
+        let child = create_window(display, "child");
+        set_pid(display, child, std::process::id());
+
+        let wm = WindowManager::new();
+        let mut rule = WindowRule::default();
+        rule.title = Some("terminal".to_string());
+        rule.actions = RuleActions { terminal: Some(true), ..Default::default() };
+        wm.config.lock().unwrap().rules.push(rule);
+
+        wm.manage_system_window(terminal as u64).unwrap();
+        let (_, tile) = window_geometry(display, terminal);
+        assert_ne!(tile, (0, 0, 200, 100), "the terminal was not moved into its tile");
+
+        // The child takes over the terminal's tile, and the terminal is hidden
+        let child_id = wm.manage_system_window(child as u64).unwrap();
+        assert_eq!(window_geometry(display, child), (IsViewable, tile));
+        assert_eq!(window_geometry(display, terminal).0, IsUnmapped);
+
+        wm.remove_window(&child_id).unwrap();
+        assert_eq!(window_geometry(display, terminal), (IsViewable, tile));
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn system_window_tabs_leave_out_floating_windows() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let first = create_window(display, "first tab");
+        let dialog = create_window(display, "floating dialog");
+        let second = create_window(display, "second tab");
+
+        let wm = WindowManager::new();
+        let mut rule = WindowRule::default();
+        rule.title = Some("floating dialog".to_string());
+        rule.actions = RuleActions { floating: Some(true), ..Default::default() };
+        wm.config.lock().unwrap().rules.push(rule);
+        wm.get_system_windows().unwrap();
+
+        let handles = [first as u64, dialog as u64, second as u64];
+        let tabs = wm.get_system_window_tabs(&handles, "tabbed", Some(second as u64));
+        let summary: Vec<(String, usize, bool, Rect)> = tabs
+            .into_iter()
+            .map(|tab| (tab.window_id, tab.index, tab.active, tab.header))
+            .collect();
+        let header = |x| Rect { x, y: 0, width: 640, height: 24 };
+        assert_eq!(summary, [
+            (first.to_string(), 0, false, header(0)),
+            (second.to_string(), 1, true, header(640)),
+        ]);
+
+        // The headers sit on the windows the layout tiles
+        wm.arrange_system_windows_with_layout(&handles, "tabbed", Some(second as u64)).unwrap();
+        assert_eq!(window_geometry(display, second).1, (0, 24, 1280, 1000));
+
+        XCloseDisplay(display);
+    }
+}
+
+#[test]
+fn strut_changes_are_reported() {
+    let Some(_server) = server() else {
+        return;
+    };
+
+    unsafe {
+        let display = open_display();
+        let events = X11Backend::global().unwrap().subscribe().unwrap();
+        let panel = create_window(display, "panel");
+        // Wait for the backend to start watching the new window
+        let created = std::iter::from_fn(|| events.recv_timeout(Duration::from_secs(5)).ok())
+            .any(|event| event == SystemWindowEvent::Created { handle: panel as u64 });
+        assert!(created);
+
+        let strut = CString::new("_NET_WM_STRUT").unwrap();
+        let strut = XInternAtom(display, strut.as_ptr(), False);
+        let values: [c_ulong; 4] = [0, 0, 0, 32];
+        XChangeProperty(
+            display, panel, strut, XA_CARDINAL, 32, PropModeReplace,
+            values.as_ptr() as *const u8, values.len() as c_int,
+        );
+        XSync(display, False);
+
+        let changed = std::iter::from_fn(|| events.recv_timeout(Duration::from_secs(5)).ok())
+            .any(|event| event == SystemWindowEvent::WorkAreaChanged);
+        assert!(changed);
+
+        XCloseDisplay(display);
+    }
+}